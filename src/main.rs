@@ -12,7 +12,17 @@ mod screens;
 mod theme;
 mod util;
 
-use bevy::{asset::AssetMetaCheck, prelude::*};
+use std::{path::PathBuf, time::Duration};
+
+use bevy::{
+    app::ScheduleRunnerPlugin,
+    asset::AssetMetaCheck,
+    prelude::*,
+    render::{RenderPlugin, settings::WgpuSettings},
+    time::TimeUpdateStrategy,
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
 
 fn main() -> AppExit {
     App::new().add_plugins(AppPlugin).run()
@@ -22,26 +32,54 @@ pub struct AppPlugin;
 
 impl Plugin for AppPlugin {
     fn build(&self, app: &mut App) {
+        let args = LaunchArgs::from_env();
+
         // Add Bevy plugins.
-        app.add_plugins(
-            DefaultPlugins
-                .set(AssetPlugin {
-                    // Wasm builds will check for meta files (that don't exist) if this isn't set.
-                    // This causes errors and even panics on web build on itch.
-                    // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
-                    meta_check: AssetMetaCheck::Never,
+        let default_plugins = DefaultPlugins
+            .set(AssetPlugin {
+                // Wasm builds will check for meta files (that don't exist) if this isn't set.
+                // This causes errors and even panics on web build on itch.
+                // See https://github.com/bevyengine/bevy_github_ci_template/issues/48.
+                meta_check: AssetMetaCheck::Never,
+                ..default()
+            })
+            .set(WindowPlugin {
+                primary_window: Window {
+                    title: "Faster Than Vampires".to_string(),
+                    fit_canvas_to_parent: true,
                     ..default()
-                })
-                .set(WindowPlugin {
-                    primary_window: Window {
-                        title: "Faster Than Vampires".to_string(),
-                        fit_canvas_to_parent: true,
+                }
+                .into(),
+                ..default()
+            });
+
+        if args.check_replay {
+            // No window or GPU, and exactly one fixed timestep per update, as fast as possible.
+            app.add_plugins((
+                default_plugins
+                    .set(WindowPlugin {
+                        primary_window: None,
+                        exit_condition: ExitCondition::DontExit,
                         ..default()
-                    }
-                    .into(),
-                    ..default()
-                }),
-        );
+                    })
+                    .set(RenderPlugin {
+                        render_creation: WgpuSettings {
+                            backends: None,
+                            ..default()
+                        }
+                        .into(),
+                        ..default()
+                    })
+                    .disable::<WinitPlugin>(),
+                ScheduleRunnerPlugin::run_loop(Duration::ZERO),
+            ));
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(
+                Time::<Fixed>::default().timestep(),
+            ));
+        } else {
+            app.add_plugins(default_plugins);
+        }
+        app.insert_resource(args);
         app.add_plugins(avian2d::PhysicsPlugins::default().with_length_unit(100.0));
 
         // Add other plugins.
@@ -88,6 +126,37 @@ enum AppSystems {
     Update,
 }
 
+/// Options given on the command line.
+#[derive(Resource, Clone, Default, Debug)]
+struct LaunchArgs {
+    /// `--replay <file>`: play back a recorded encounter as soon as the game has loaded.
+    replay: Option<PathBuf>,
+    /// `--check-replay <file>`: play it back headless, then exit with whether it
+    /// reproduced the recorded result.
+    check_replay: bool,
+}
+
+impl LaunchArgs {
+    fn from_env() -> Self {
+        let mut parsed = Self::default();
+        let mut args = std::env::args().skip(1);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--replay" => parsed.replay = args.next().map(PathBuf::from),
+                "--check-replay" => {
+                    parsed.replay = args.next().map(PathBuf::from);
+                    parsed.check_replay = true;
+                }
+                // logging isn't set up yet
+                _ => eprintln!("ignoring unknown argument `{arg}`"),
+            }
+        }
+
+        parsed
+    }
+}
+
 /// Whether or not the game is paused.
 #[derive(States, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
 #[states(scoped_entities)]
@@ -3,6 +3,7 @@
 mod credits;
 mod main;
 mod pause;
+mod seed;
 mod settings;
 
 use bevy::prelude::*;
@@ -15,6 +16,7 @@ pub(super) fn plugin(app: &mut App) {
         main::plugin,
         settings::plugin,
         pause::plugin,
+        seed::plugin,
     ));
 }
 
@@ -28,4 +30,6 @@ pub enum Menu {
     Settings,
     Pause,
     Buy,
+    Practice,
+    Seed,
 }
@@ -0,0 +1,90 @@
+//! The seed menu, for playing a galaxy someone else shared. The seed is typed in as
+//! hex, the same way the pause menu shows it.
+
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use crate::{menus::Menu, screens::GalaxySeed, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::Seed), spawn_seed_menu);
+    app.add_systems(
+        Update,
+        (type_seed, update_seed_label)
+            .chain()
+            .run_if(in_state(Menu::Seed)),
+    );
+
+    app.register_type::<SeedLabel>();
+}
+
+fn spawn_seed_menu(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Seed Menu"),
+        GlobalZIndex(2),
+        StateScoped(Menu::Seed),
+        children![
+            widget::header("Galaxy seed"),
+            (widget::label(""), SeedLabel),
+            widget::label("Type a seed, or leave it empty for a new galaxy every run"),
+            widget::button("Random", clear_seed),
+            widget::button("Back", go_back_on_click),
+        ],
+    ));
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SeedLabel;
+
+fn update_seed_label(seed: Res<GalaxySeed>, mut label: Single<&mut Text, With<SeedLabel>>) {
+    label.0 = match **seed {
+        Some(seed) => format!("{seed:X}"),
+        None => "Random".to_string(),
+    };
+}
+
+/// Hex digits add to the seed, backspace takes the last one off again.
+fn type_seed(
+    mut keys: EventReader<KeyboardInput>,
+    mut seed: ResMut<GalaxySeed>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    for key in keys.read() {
+        if key.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &key.logical_key {
+            Key::Character(text) => {
+                let Some(digit) = text.chars().next().and_then(|c| c.to_digit(16)) else {
+                    continue;
+                };
+                let current = seed.unwrap_or(0);
+                // out of room for another digit
+                if current.leading_zeros() < 4 {
+                    continue;
+                }
+                **seed = Some(current << 4 | digit as u64);
+            }
+            Key::Backspace => {
+                **seed = seed.map(|seed| seed >> 4).filter(|&seed| seed != 0);
+            }
+            Key::Enter | Key::Escape => next_menu.set(Menu::Main),
+            _ => {}
+        }
+    }
+}
+
+fn clear_seed(_: Trigger<Pointer<Click>>, mut seed: ResMut<GalaxySeed>) {
+    **seed = None;
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
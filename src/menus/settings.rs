@@ -4,7 +4,11 @@
 
 use bevy::{audio::Volume, input::common_conditions::input_just_pressed, prelude::*, ui::Val::*};
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    menus::Menu,
+    screens::{Difficulty, Screen},
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
@@ -18,6 +22,12 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         update_global_volume_label.run_if(in_state(Menu::Settings)),
     );
+
+    app.register_type::<DifficultyLabel>();
+    app.add_systems(
+        Update,
+        update_difficulty_label.run_if(in_state(Menu::Settings)),
+    );
 }
 
 fn spawn_settings_menu(mut commands: Commands) {
@@ -52,6 +62,14 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("Difficulty"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            difficulty_widget(),
         ],
     )
 }
@@ -104,6 +122,48 @@ fn update_global_volume_label(
     label.0 = format!("{percent:3.0}%");
 }
 
+fn difficulty_widget() -> impl Bundle {
+    (
+        Name::new("Difficulty Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower_difficulty),
+            (
+                Name::new("Current Difficulty"),
+                Node {
+                    padding: UiRect::horizontal(Px(10.0)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), DifficultyLabel)],
+            ),
+            widget::button_small("+", raise_difficulty),
+        ],
+    )
+}
+
+fn lower_difficulty(_: Trigger<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.easier();
+}
+
+fn raise_difficulty(_: Trigger<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = difficulty.harder();
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DifficultyLabel;
+
+fn update_difficulty_label(
+    difficulty: Res<Difficulty>,
+    mut label: Single<&mut Text, With<DifficultyLabel>>,
+) {
+    label.0 = difficulty.name().to_string();
+}
+
 fn go_back_on_click(
     _: Trigger<Pointer<Click>>,
     screen: Res<State<Screen>>,
@@ -1,9 +1,21 @@
 //! The screen state for the main gameplay.
+mod bounds;
 mod combat;
+mod culling;
+mod director;
 mod enemies;
+mod galaxy;
+mod gravity;
+mod hazards;
 mod level;
+mod minimap;
 mod movement;
+mod parallax;
 mod player;
+mod salvage;
+mod sector;
+mod timeline;
+mod touhou;
 mod upgrade_menu;
 
 mod animation;
@@ -17,19 +29,51 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*, ui::Val::*}
 use crate::{menus::Menu, screens::Screen, PausableSystems, Pause};
 use level::spawn_level;
 
+pub use galaxy::{Galaxy, GalaxySeed};
+pub use touhou::Difficulty;
+
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 struct GameplayLogic;
 
+/// Which part of the game is being played on the gameplay screen.
+#[derive(SubStates, Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+#[source(Screen = Screen::Gameplay)]
+#[states(scoped_entities)]
+pub enum GameState {
+    /// Flying through the sector, shopping at planets.
+    #[default]
+    OpenWorld,
+    /// The bullet-hell encounter against the flagship.
+    Touhou,
+}
+
 pub(super) fn plugin(app: &mut App) {
     app.add_plugins((
         level::plugin,
+        galaxy::plugin,
+        sector::plugin,
         player::plugin,
         movement::plugin,
+        parallax::plugin,
         enemies::plugin,
         upgrade_menu::plugin,
         combat::plugin,
         animation::plugin,
+        touhou::plugin,
     ));
+    // Open world rules on top of the level.
+    app.add_plugins((
+        gravity::plugin,
+        bounds::plugin,
+        culling::plugin,
+        hazards::plugin,
+        director::plugin,
+        minimap::plugin,
+        salvage::plugin,
+        timeline::plugin,
+    ));
+
+    app.add_sub_state::<GameState>();
 
     app.add_systems(OnEnter(Screen::Gameplay), spawn_level);
 
@@ -48,7 +92,7 @@ pub(super) fn plugin(app: &mut App) {
                     .and(input_just_pressed(KeyCode::KeyP)),
             ),
             (pause, spawn_pause_overlay, open_buy_menu).run_if(
-                in_state(Screen::Gameplay)
+                in_state(GameState::OpenWorld)
                     .and(in_state(Menu::None))
                     .and(input_just_pressed(KeyCode::KeyB)),
             ),
@@ -66,17 +110,26 @@ pub(super) fn plugin(app: &mut App) {
             resource_exists::<level::LevelAssets>
                 .and(resource_exists::<player::PlayerAssets>)
                 .and(resource_exists::<enemies::EntityAssets>)
+                .and(resource_exists::<galaxy::Galaxy>)
                 .and(any_with_component::<player::Player>)
-                .and(any_with_component::<Camera2d>),
+                .and(any_with_component::<Camera2d>)
+                .and(in_state(GameState::OpenWorld)),
         ),
     );
 
     app.insert_resource(Gravity(Vec2::ZERO));
 }
 
-fn unpause(mut next_pause: ResMut<NextState<Pause>>, mut time: ResMut<Time<Physics>>) {
+fn unpause(
+    mut next_pause: ResMut<NextState<Pause>>,
+    mut time: ResMut<Time<Physics>>,
+    game_state: Option<Res<State<GameState>>>,
+) {
     next_pause.set(Pause(false));
-    time.unpause();
+    // The open world stays frozen for as long as the encounter lasts.
+    if game_state.is_none_or(|state| *state.get() != GameState::Touhou) {
+        time.unpause();
+    }
 }
 
 fn pause(mut next_pause: ResMut<NextState<Pause>>, mut time: ResMut<Time<Physics>>) {
@@ -0,0 +1,158 @@
+//! The edge of the sector. Close to it the player gets a warning, past it they're pushed
+//! back and their hull starts to give, worse the longer they stay out.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{AppSystems, PausableSystems};
+
+use super::{
+    GameState, GameplayLogic, combat::Damage, galaxy::Galaxy, level::UIAssets, player::Player,
+};
+
+/// How close to the edge the warning shows up.
+const WARNING_DISTANCE: f32 = 1500.0;
+/// Past the edge the player gets pushed back this hard for every unit they're out, in
+/// units/s².
+const PUSH_BACK: f32 = 0.5;
+/// Out of bounds damage is dealt once a second, this much more every time.
+const DAMAGE_STEP: i32 = 5;
+const WARNING_COLOR: Color = Color::srgba(1.0, 0.2, 0.1, 0.8);
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<OutOfBounds>();
+    app.add_systems(OnExit(GameState::OpenWorld), reset_out_of_bounds);
+
+    app.add_systems(
+        FixedUpdate,
+        push_back
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GameState::OpenWorld).and(resource_exists::<Galaxy>)),
+    );
+    app.add_systems(
+        Update,
+        (out_of_bounds_damage, update_edge_warning)
+            .chain()
+            .in_set(GameplayLogic),
+    );
+}
+
+/// How long the player has been out of bounds.
+#[derive(Resource)]
+struct OutOfBounds {
+    timer: Timer,
+    ticks: i32,
+}
+
+impl Default for OutOfBounds {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            ticks: 0,
+        }
+    }
+}
+
+#[derive(Component)]
+struct EdgeWarning;
+
+/// How far inside the bounds `position` is, negative once it's outside.
+fn distance_to_edge(bounds: Rect, position: Vec2) -> f32 {
+    if bounds.contains(position) {
+        (position - bounds.min)
+            .min(bounds.max - position)
+            .min_element()
+    } else {
+        -position.distance(position.clamp(bounds.min, bounds.max))
+    }
+}
+
+fn reset_out_of_bounds(mut out_of_bounds: ResMut<OutOfBounds>) {
+    *out_of_bounds = OutOfBounds::default();
+}
+
+fn push_back(
+    time: Res<Time>,
+    galaxy: Res<Galaxy>,
+    player: Single<(&Transform, &mut LinearVelocity), With<Player>>,
+) {
+    let (trans, mut velocity) = player.into_inner();
+    let position = trans.translation.xy();
+    let outside = position.clamp(galaxy.bounds.min, galaxy.bounds.max) - position;
+
+    velocity.0 += outside * PUSH_BACK * time.delta_secs();
+}
+
+fn out_of_bounds_damage(
+    mut commands: Commands,
+    time: Res<Time>,
+    galaxy: Res<Galaxy>,
+    mut out_of_bounds: ResMut<OutOfBounds>,
+    player: Single<(Entity, &Transform), With<Player>>,
+) {
+    let (player, trans) = player.into_inner();
+    if galaxy.bounds.contains(trans.translation.xy()) {
+        *out_of_bounds = OutOfBounds::default();
+        return;
+    }
+
+    if out_of_bounds.timer.tick(time.delta()).just_finished() {
+        out_of_bounds.ticks += 1;
+        commands.trigger_targets(Damage(DAMAGE_STEP * out_of_bounds.ticks), player);
+    }
+}
+
+fn update_edge_warning(
+    mut commands: Commands,
+    galaxy: Res<Galaxy>,
+    ui_assets: Res<UIAssets>,
+    player: Single<&Transform, With<Player>>,
+    warnings: Query<(Entity, &Children), With<EdgeWarning>>,
+    mut texts: Query<&mut Text>,
+) {
+    let distance = distance_to_edge(galaxy.bounds, player.translation.xy());
+    let message = if distance < 0.0 {
+        "OUT OF BOUNDS - hull failing"
+    } else if distance < WARNING_DISTANCE {
+        "LEAVING THE SECTOR - turn back"
+    } else {
+        for (warning, _) in &warnings {
+            commands.entity(warning).despawn();
+        }
+        return;
+    };
+
+    if let Ok((_, children)) = warnings.single() {
+        if let Ok(mut text) = texts.get_mut(children[0]) {
+            text.0 = message.to_string();
+        }
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Edge Warning"),
+        EdgeWarning,
+        StateScoped(GameState::OpenWorld),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            border: UiRect::all(Val::Px(12.0)),
+            ..default()
+        },
+        BorderColor(WARNING_COLOR),
+        Pickable::IGNORE,
+        children![(
+            Text::new(message),
+            TextFont {
+                font: ui_assets.font.clone(),
+                font_size: 36.0,
+                ..default()
+            },
+            TextColor(WARNING_COLOR),
+        )],
+    ));
+}
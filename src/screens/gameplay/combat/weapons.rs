@@ -14,7 +14,9 @@ use crate::{
     screens::{
         gameplay::{
             animation::{AnimatedSprite, AnimationType},
+            culling::{Dormant, Lifetime},
             enemies::{ContinuosDamage, Enemy, EvilContinuousDamage, FlagshipAI},
+            hazards::HazardState,
             player::Player,
             GameplayLogic,
         },
@@ -32,6 +34,8 @@ pub fn plugin(app: &mut App) {
         Update,
         (
             fire_cannon,
+            fire_pirate_guns,
+            fire_turrets,
             rotate_orbs,
             process_blackhole_spawners,
             process_blackholes,
@@ -68,6 +72,10 @@ pub struct WeaponAssets {
     pub e_field_big: Handle<Image>,
     pub e_field_big_layout: Handle<TextureAtlasLayout>,
 
+    #[dependency]
+    pub enemy_shot: Handle<Image>,
+    pub enemy_shot_layout: Handle<TextureAtlasLayout>,
+
     #[dependency]
     pub muzzle_flash: Handle<Image>,
     pub muzzle_flash_layout: Handle<TextureAtlasLayout>,
@@ -155,6 +163,9 @@ impl FromWorld for WeaponAssets {
             cannon: assets.load_with_settings("images/entities/Gun1.png", make_nearest),
             laser_shot: assets.load("VFX/Flipbooks/TFlip_LaserBall.png"),
             laser_shot_layout: assets.add(mkatlas(32, 5, 3)),
+            enemy_shot: assets
+                .load_with_settings("VFX/Flipbooks/TFlip_LaserBall_Lower.png", make_nearest),
+            enemy_shot_layout: assets.add(mkatlas(16, 5, 3)),
             muzzle_flash: assets.load_with_settings("VFX/Flipbooks/TFlip_Blast.png", make_nearest),
             muzzle_flash_layout: assets.add(TextureAtlasLayout::from_grid(
                 UVec2::splat(86),
@@ -257,6 +268,9 @@ pub struct Cannon {
 #[derive(Component)]
 pub struct CannonBullet;
 
+/// Seconds before a bullet that didn't hit anything is gone.
+const CANNON_BULLET_LIFETIME: f32 = 3.0;
+
 pub fn spawn_cannons(cannon: &Handle<Image>, n: usize) -> Vec<impl Bundle> {
     let positions = match n {
         0 => vec![],
@@ -423,6 +437,7 @@ pub fn fire_cannon(
                 AnimatedSprite::new(30, 15, AnimationType::Repeating),
                 Collider::circle(10.0),
                 CannonBullet,
+                Lifetime::from_secs(CANNON_BULLET_LIFETIME),
                 RigidBody::Kinematic,
                 CollisionEventsEnabled,
                 Sensor,
@@ -466,6 +481,174 @@ pub fn fire_cannon(
     }
 }
 
+/// Pirates only shoot at the player from this close.
+const PIRATE_GUN_RANGE: f32 = 900.0;
+/// Turrets reach a bit further.
+const TURRET_RANGE: f32 = 1400.0;
+/// How fast turrets turn, in radians/s.
+const TURRET_TURN_RATE: f32 = 2.0;
+/// Turrets hold fire until they're aimed at least this close, in radians.
+const TURRET_AIM_TOLERANCE: f32 = 0.15;
+const ENEMY_BULLET_SPEED: f32 = 350.0;
+const ENEMY_BULLET_DAMAGE: i32 = 10;
+const ENEMY_BULLET_LIFETIME: f32 = 4.0;
+
+/// The gun on a pirate ship, see [`PirateAI`](crate::screens::gameplay::enemies::PirateAI).
+#[derive(Component)]
+pub struct PirateGun {
+    timer: Timer,
+}
+
+impl Default for PirateGun {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(1.5, TimerMode::Repeating),
+        }
+    }
+}
+
+/// A gun on an outpost. It turns to follow the player, it points up when its rotation
+/// is zero.
+#[derive(Component)]
+pub struct OutpostTurret {
+    timer: Timer,
+}
+
+impl Default for OutpostTurret {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Shot by pirates and outposts, only hurts the player.
+#[derive(Component)]
+pub struct EnemyBullet;
+
+/// Where to aim from `pos` to hit something at `target` moving with `velocity`, using
+/// where it will be by the time a bullet gets there.
+fn lead(pos: Vec2, target: Vec2, velocity: Vec2) -> Vec2 {
+    let ahead = target + velocity * pos.distance(target) / ENEMY_BULLET_SPEED;
+    (ahead - pos).normalize_or_zero()
+}
+
+fn fire_enemy_bullet(commands: &mut Commands, assets: &WeaponAssets, pos: Vec2, dir: Vec2) {
+    //Spawning bullet
+    commands
+        .spawn((
+            Name::new("Enemy Bullet"),
+            Sprite {
+                image: assets.enemy_shot.clone(),
+                texture_atlas: Some(TextureAtlas {
+                    layout: assets.enemy_shot_layout.clone(),
+                    index: 0,
+                }),
+                custom_size: Some(Vec2::splat(24.0)),
+                color: Color::srgb(1.0, 0.4, 0.3),
+                ..default()
+            },
+            StateScoped(Screen::Gameplay),
+            Transform::from_translation(pos.extend(0.0)),
+            AnimatedSprite::new(30, 15, AnimationType::Repeating),
+            Collider::circle(8.0),
+            EnemyBullet,
+            Lifetime::from_secs(ENEMY_BULLET_LIFETIME),
+            RigidBody::Kinematic,
+            CollisionEventsEnabled,
+            Sensor,
+            LinearVelocity(dir * ENEMY_BULLET_SPEED),
+        ))
+        .observe(
+            |trigger: Trigger<OnCollisionStart>,
+             mut commands: Commands,
+             player: Single<Entity, With<Player>>| {
+                if trigger.collider != *player {
+                    return;
+                }
+                commands.trigger_targets(Damage(ENEMY_BULLET_DAMAGE), trigger.collider);
+                commands.entity(trigger.target()).despawn();
+            },
+        );
+
+    //Spawning muzzle flash
+    commands.spawn((
+        Sprite {
+            image: assets.muzzle_flash.clone(),
+            custom_size: Some(Vec2 { x: 48.0, y: 48.0 }),
+            texture_atlas: Some(TextureAtlas {
+                layout: assets.muzzle_flash_layout.clone(),
+                index: 0,
+            }),
+            color: Color::srgb(1.0, 0.5, 0.4),
+            ..default()
+        },
+        StateScoped(Screen::Gameplay),
+        Transform::from_translation(pos.extend(0.1)),
+        AnimatedSprite::new(15, 9, AnimationType::Once),
+    ));
+}
+
+/// Pirates lead their shots, aiming where the player will be when the bullet gets there.
+fn fire_pirate_guns(
+    mut commands: Commands,
+    player: Single<(&LinearVelocity, &Transform), With<Player>>,
+    pirates: Query<(&Transform, &mut PirateGun), Without<Dormant>>,
+    assets: Res<WeaponAssets>,
+    time: Res<Time>,
+) {
+    let (player_velocity, player_trans) = player.into_inner();
+    let player_pos = player_trans.translation.xy();
+
+    for (pirate_trans, mut gun) in pirates {
+        let pos = pirate_trans.translation.xy();
+        if pos.distance(player_pos) > PIRATE_GUN_RANGE {
+            continue;
+        }
+
+        gun.timer.tick(time.delta());
+        if !gun.timer.just_finished() {
+            continue;
+        }
+
+        let dir = lead(pos, player_pos, player_velocity.0);
+        fire_enemy_bullet(&mut commands, &assets, pos + dir * 40.0, dir);
+    }
+}
+
+/// Turrets turn towards where the player is going and fire once they're aimed.
+fn fire_turrets(
+    mut commands: Commands,
+    player: Single<(&LinearVelocity, &Transform), With<Player>>,
+    turrets: Query<(&GlobalTransform, &mut Transform, &mut OutpostTurret), Without<Player>>,
+    assets: Res<WeaponAssets>,
+    time: Res<Time>,
+) {
+    let (player_velocity, player_trans) = player.into_inner();
+    let player_pos = player_trans.translation.xy();
+
+    for (global_transform, mut transform, mut turret) in turrets {
+        let pos = global_transform.translation().xy();
+        if pos.distance(player_pos) > TURRET_RANGE {
+            continue;
+        }
+
+        // Outposts don't turn, so the turret's own rotation is the one in the world.
+        let facing = (transform.rotation * Vec3::Y).xy();
+        let dir = lead(pos, player_pos, player_velocity.0);
+        let turn = facing.angle_to(dir);
+        let step = TURRET_TURN_RATE * time.delta_secs();
+        transform.rotate_z(turn.clamp(-step, step));
+
+        turret.timer.tick(time.delta());
+        if !turret.timer.just_finished() || turn.abs() > TURRET_AIM_TOLERANCE {
+            continue;
+        }
+
+        fire_enemy_bullet(&mut commands, &assets, pos + facing * 30.0, facing);
+    }
+}
+
 const LASER_FIRE_TIME: u64 = 2000;
 const LASER_COOLDOWN_TIME: u64 = 4000;
 
@@ -552,6 +735,7 @@ fn fire_laser(
     lasers: Query<(Entity, &mut Laser, &RayHits, &RayCaster, Option<&Children>)>,
     enemies: Query<Entity, With<Enemy>>,
     mut laser_sprite: Query<&mut LaserBeam>,
+    hazards: Res<HazardState>,
 ) {
     for (laser_ent_id, mut laser, ray_hits, raycaster, children) in lasers {
         laser.update_timer(timer.delta());
@@ -565,7 +749,7 @@ fn fire_laser(
             None => raycaster.max_distance,
         };
 
-        if !laser.firing {
+        if !laser.firing || hazards.jams_weapons() {
             laser_ent.despawn_related::<Children>();
             continue;
         } else {
@@ -0,0 +1,89 @@
+//! Keeps the open world from filling up with things the player left behind. Anything
+//! marked [`Cull`] goes to sleep once it's far enough from the player and is despawned
+//! once it's further still, and anything with a [`Lifetime`] is despawned when it runs
+//! out.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use super::{GameplayLogic, player::Player};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CullSettings>();
+    app.init_resource::<CullSettings>();
+
+    app.add_systems(
+        Update,
+        (cull_by_distance, expire_lifetimes).in_set(GameplayLogic),
+    );
+}
+
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct CullSettings {
+    /// Further than this from the player, physics and AI stop running.
+    pub sleep_distance: f32,
+    /// Further than this, things are gone for good.
+    pub despawn_distance: f32,
+}
+
+impl Default for CullSettings {
+    fn default() -> Self {
+        Self {
+            sleep_distance: 2500.0,
+            despawn_distance: 6000.0,
+        }
+    }
+}
+
+/// Put to sleep and despawned depending on how far it is from the player.
+#[derive(Component)]
+pub struct Cull;
+
+/// Set on culled entities while they're asleep. AI should skip them.
+#[derive(Component)]
+pub struct Dormant;
+
+#[derive(Component)]
+pub struct Lifetime(pub Timer);
+
+impl Lifetime {
+    pub fn from_secs(secs: f32) -> Self {
+        Self(Timer::from_seconds(secs, TimerMode::Once))
+    }
+}
+
+fn cull_by_distance(
+    mut commands: Commands,
+    settings: Res<CullSettings>,
+    player: Single<&Transform, With<Player>>,
+    culled: Query<(Entity, &Transform, Has<Dormant>), With<Cull>>,
+) {
+    let player_pos = player.translation.xy();
+    for (ent, trans, dormant) in &culled {
+        let distance = trans.translation.xy().distance(player_pos);
+        if distance > settings.despawn_distance {
+            commands.entity(ent).despawn();
+        } else if distance > settings.sleep_distance {
+            if !dormant {
+                commands.entity(ent).insert((Dormant, RigidBodyDisabled));
+            }
+        } else if dormant {
+            commands
+                .entity(ent)
+                .remove::<(Dormant, RigidBodyDisabled)>();
+        }
+    }
+}
+
+fn expire_lifetimes(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lifetimes: Query<(Entity, &mut Lifetime)>,
+) {
+    for (ent, mut lifetime) in &mut lifetimes {
+        if lifetime.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn();
+        }
+    }
+}
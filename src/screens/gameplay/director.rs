@@ -0,0 +1,207 @@
+//! Decides when the open world spawns enemies. Every biome fills up a threat budget
+//! over time, which the director spends on the biome's enemies by their `cost`. It
+//! spends faster the longer the run goes on and the closer the flagship gets, but holds
+//! off while the biome's `max_alive` of its enemies are still around the player.
+//!
+//! Press F3 to see what it's doing.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::screens::Screen;
+
+use super::{
+    GameplayLogic,
+    enemies::{EntityAssets, FlagshipAI},
+    galaxy::Galaxy,
+    level::{UIAssets, spawn_enemy},
+    player::Player,
+    sector::EnemyMix,
+};
+
+/// How much faster the budget fills for every minute of the run...
+const RAMP_PER_MINUTE: f32 = 0.2;
+/// ...up to this many times as fast.
+const MAX_TIME_RAMP: f32 = 2.0;
+/// With the flagship right behind the player the budget fills this much faster on top.
+const FLAGSHIP_PRESSURE: f32 = 1.0;
+/// How close the flagship has to be before it starts making things worse.
+const FLAGSHIP_PRESSURE_RANGE: f32 = 6000.0;
+/// How many seconds worth of budget can be saved up, so leaving enemies alive doesn't
+/// end in a flood once they're gone.
+const MAX_BANKED_SECS: f32 = 3.0;
+/// Enemies further away than this don't count towards `max_alive` anymore.
+const ACTIVE_RANGE: f32 = 2500.0;
+
+const OVERLAY_KEY: KeyCode = KeyCode::F3;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SpawnDirector>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_director);
+    app.add_systems(Update, run_director.in_set(GameplayLogic));
+
+    app.add_systems(
+        Update,
+        (
+            toggle_overlay.run_if(input_just_pressed(OVERLAY_KEY)),
+            update_overlay,
+        )
+            .chain()
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct SpawnDirector {
+    /// The biome the player is in, the budget starts over when it changes.
+    biome: Option<String>,
+    budget: f32,
+    /// The most budget that can be saved up right now.
+    cap: f32,
+    /// Budget per second, ramp included.
+    rate: f32,
+    /// Picked ahead of time, so cheap enemies can't keep the expensive ones from ever
+    /// being afforded.
+    next: Option<EnemyMix>,
+    /// Seconds spent in the open world this run.
+    run_time: f32,
+    time_ramp: f32,
+    flagship_ramp: f32,
+    alive: usize,
+    max_alive: usize,
+    spawned: usize,
+}
+
+impl SpawnDirector {
+    fn ramp(&self) -> f32 {
+        self.time_ramp * self.flagship_ramp
+    }
+}
+
+/// Marks enemies the director spawned, so it knows how many are still around.
+#[derive(Component)]
+pub struct DirectorSpawned;
+
+fn reset_director(mut director: ResMut<SpawnDirector>) {
+    *director = SpawnDirector::default();
+}
+
+fn run_director(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut director: ResMut<SpawnDirector>,
+    galaxy: Res<Galaxy>,
+    entity_assets: Res<EntityAssets>,
+    player: Single<&Transform, With<Player>>,
+    flagship: Single<&Transform, With<FlagshipAI>>,
+    spawned: Query<&Transform, With<DirectorSpawned>>,
+) {
+    let player_pos = player.translation;
+    director.run_time += time.delta_secs();
+
+    director.time_ramp = (1.0 + director.run_time / 60.0 * RAMP_PER_MINUTE).min(MAX_TIME_RAMP);
+    let flagship_distance = player_pos.xy().distance(flagship.translation.xy());
+    director.flagship_ramp = 1.0
+        + FLAGSHIP_PRESSURE * (1.0 - flagship_distance / FLAGSHIP_PRESSURE_RANGE).clamp(0.0, 1.0);
+
+    director.alive = spawned
+        .iter()
+        .filter(|trans| trans.translation.xy().distance(player_pos.xy()) < ACTIVE_RANGE)
+        .count();
+
+    let Some(biome) = galaxy.biome_at(player_pos.xy()) else {
+        director.biome = None;
+        director.budget = 0.0;
+        director.next = None;
+        return;
+    };
+    if director.biome.as_ref() != Some(&biome.name) {
+        director.biome = Some(biome.name.clone());
+        director.budget = 0.0;
+        director.next = None;
+    }
+    director.max_alive = biome.max_alive;
+
+    let mut rng = rand::rng();
+    if director.next.is_none() {
+        director.next = biome.pick_enemy(&mut rng);
+    }
+    let Some(next) = director.next else {
+        return;
+    };
+
+    director.rate = biome.threat_rate * director.ramp();
+    director.cap = (director.rate * MAX_BANKED_SECS).max(next.cost);
+    director.budget = (director.budget + director.rate * time.delta_secs()).min(director.cap);
+
+    // one a frame is plenty, and keeps them from all showing up in the same spot
+    if director.budget < next.cost || director.alive >= biome.max_alive {
+        return;
+    }
+    let angle = biome.pattern.angle(&mut rng);
+    let enemy = spawn_enemy(&mut commands, &entity_assets, next, angle, player_pos);
+    commands.entity(enemy).insert(DirectorSpawned);
+
+    director.budget -= next.cost;
+    director.next = biome.pick_enemy(&mut rng);
+    director.spawned += 1;
+}
+
+#[derive(Component)]
+struct DirectorOverlay;
+
+fn toggle_overlay(
+    mut commands: Commands,
+    overlay: Query<Entity, With<DirectorOverlay>>,
+    ui_assets: Res<UIAssets>,
+) {
+    if let Ok(overlay) = overlay.single() {
+        commands.entity(overlay).despawn();
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Spawn Director Overlay"),
+        DirectorOverlay,
+        StateScoped(Screen::Gameplay),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            right: Val::Px(8.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+    ));
+}
+
+fn update_overlay(
+    director: Res<SpawnDirector>,
+    mut overlay: Query<&mut Text, With<DirectorOverlay>>,
+) {
+    let Ok(mut overlay) = overlay.single_mut() else {
+        return;
+    };
+
+    let next = match director.next {
+        Some(next) => format!("{:?}/{:?} ({})", next.ai, next.look, next.cost),
+        None => "nothing".to_string(),
+    };
+    overlay.0 = format!(
+        "biome: {}\nbudget: {:.1} / {:.1} (+{:.1}/s)\nramp: {:.2} (time {:.2} x flagship {:.2})\nalive: {} / {}\nnext: {next}\nspawned: {}",
+        director.biome.as_deref().unwrap_or("none"),
+        director.budget,
+        director.cap,
+        director.rate,
+        director.ramp(),
+        director.time_ramp,
+        director.flagship_ramp,
+        director.alive,
+        director.max_alive,
+        director.spawned,
+    );
+}
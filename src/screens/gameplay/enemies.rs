@@ -2,18 +2,26 @@ use std::{f32::consts::PI, time::Instant};
 
 use avian2d::prelude::*;
 use bevy::{math::VectorSpace, prelude::*};
+use rand::Rng;
+use serde::Deserialize;
 
 use crate::{asset_tracking::LoadResource, PausableSystems};
 
 use super::{
     animation::AnimatedSprite,
-    combat::{Damage, Health},
+    combat::{
+        weapons::{OutpostTurret, PirateGun},
+        Damage, Health,
+    },
+    culling::Dormant,
+    level::spawn_enemy_at,
     player::Player,
+    sector::EnemyMix,
     GameplayLogic,
 };
 
 #[repr(usize)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Deserialize)]
 pub enum ShipType {
     Flagship,
     EmpireGoon,
@@ -40,6 +48,8 @@ pub(super) fn plugin(app: &mut App) {
         (
             process_goon_ai,
             process_rammer_ai,
+            process_pirate_ai,
+            process_outpost_ai,
             process_flagship_ai,
             cont_damage_update,
             evil_cont_damage_update,
@@ -119,9 +129,7 @@ pub fn gen_goon(assets: &EntityAssets, position: Vec2) -> impl Bundle {
 
 #[derive(Component, Debug, Copy, Clone, PartialEq, Eq)]
 pub struct FlagshipAI;
-pub fn gen_flagship(assets: &EntityAssets) -> impl Bundle {
-    let position = Vec2::new(-1080.0, 0.0);
-
+pub fn gen_flagship(assets: &EntityAssets, position: Vec2) -> impl Bundle {
     let flagship = Ship {
         shiptype: ShipType::Flagship,
         lifetime: Instant::now(),
@@ -246,16 +254,19 @@ pub fn gen_rammer(
 }
 
 pub fn process_rammer_ai(
-    rammers: Query<(
-        &Transform,
-        &LinearVelocity,
-        &mut ExternalImpulse,
-        &mut LinearDamping,
-        &AngularVelocity,
-        &mut ExternalTorque,
-        &mut AngularDamping,
-        &mut RammerAI,
-    )>,
+    rammers: Query<
+        (
+            &Transform,
+            &LinearVelocity,
+            &mut ExternalImpulse,
+            &mut LinearDamping,
+            &AngularVelocity,
+            &mut ExternalTorque,
+            &mut AngularDamping,
+            &mut RammerAI,
+        ),
+        Without<Dormant>,
+    >,
     player: Single<&Transform, With<Player>>,
 ) {
     for (
@@ -309,7 +320,184 @@ pub fn process_rammer_ai(
     }
 }
 
-pub fn process_goon_ai(goons: Query<&mut Transform, With<GoonAI>>) {
+/// How far from the player pirates like to stay.
+const PIRATE_STANDOFF: f32 = 450.0;
+/// How far off the standoff distance they can be before they bother correcting it.
+const PIRATE_STANDOFF_SLACK: f32 = 75.0;
+const PIRATE_SPEED: f32 = 220.0;
+const PIRATE_STRAFE_SPEED: f32 = 160.0;
+/// How quickly they get up to speed, in units/s².
+const PIRATE_ACCELERATION: f32 = 400.0;
+
+/// Keeps its distance from the player and circles them, shooting with its [`PirateGun`].
+#[derive(Component, Debug)]
+pub struct PirateAI {
+    /// Which way around the player it's circling, 1 or -1.
+    strafe: f32,
+    /// Counts down to turning around.
+    turn: Timer,
+}
+
+impl PirateAI {
+    fn new() -> Self {
+        let mut rng = rand::rng();
+        Self {
+            strafe: if rng.random_bool(0.5) { 1.0 } else { -1.0 },
+            turn: Timer::from_seconds(rng.random_range(2.0..5.0), TimerMode::Once),
+        }
+    }
+}
+
+pub fn gen_pirate(assets: &EntityAssets, position: Vec2) -> impl Bundle {
+    let pirate = Ship {
+        shiptype: ShipType::PirateShip,
+        position,
+        lifetime: Instant::now(),
+        weapons: Vec::new(),
+    };
+    (
+        gen_enemy(pirate, assets, Vec2::ZERO),
+        PirateAI::new(),
+        PirateGun::default(),
+        ExternalImpulse::new(Vec2::ZERO),
+        Mass(1.0),
+        LinearDamping(0.8),
+        LockedAxes::ROTATION_LOCKED,
+        Health(60),
+    )
+}
+
+pub fn process_pirate_ai(
+    time: Res<Time>,
+    pirates: Query<
+        (
+            &Transform,
+            &LinearVelocity,
+            &mut ExternalImpulse,
+            &mut Rotation,
+            &mut PirateAI,
+        ),
+        Without<Dormant>,
+    >,
+    player: Single<&Transform, With<Player>>,
+) {
+    let mut rng = rand::rng();
+    for (pirate_pos, linvel, mut force, mut rotation, mut ai) in pirates {
+        if ai.turn.tick(time.delta()).finished() {
+            ai.strafe = -ai.strafe;
+            ai.turn = Timer::from_seconds(rng.random_range(2.0..5.0), TimerMode::Once);
+        }
+
+        let offset = player.translation.xy() - pirate_pos.translation.xy();
+        let distance = offset.length();
+        let to_player = offset.normalize_or_zero();
+
+        let approach = if distance > PIRATE_STANDOFF + PIRATE_STANDOFF_SLACK {
+            1.0
+        } else if distance < PIRATE_STANDOFF - PIRATE_STANDOFF_SLACK {
+            -1.0
+        } else {
+            0.0
+        };
+        let desired = to_player * approach * PIRATE_SPEED
+            + to_player.perp() * ai.strafe * PIRATE_STRAFE_SPEED;
+
+        // Mass is 1, so the impulse is the change in velocity.
+        let steer = (desired - linvel.0).clamp_length_max(PIRATE_ACCELERATION * time.delta_secs());
+        force.apply_impulse(steer);
+
+        // Always facing the player, the sprite points up.
+        *rotation = Rotation::radians(to_player.to_angle() - PI / 2.0);
+    }
+}
+
+/// Outposts only wake up with the player this close.
+const OUTPOST_RANGE: f32 = 2000.0;
+/// How many of its rammers an outpost keeps around at most.
+const OUTPOST_MAX_LAUNCHED: usize = 3;
+const OUTPOST_SIZE: Vec2 = Vec2::new(160.0, 262.0);
+
+/// A fixed defensive structure. Its [`OutpostTurret`]s shoot at the player and it
+/// launches rammers every now and then.
+#[derive(Component, Debug)]
+pub struct OutpostAI {
+    launch: Timer,
+}
+
+/// A rammer launched by the outpost.
+#[derive(Component, Debug)]
+pub struct LaunchedFrom(Entity);
+
+pub fn gen_outpost(assets: &EntityAssets, position: Vec2) -> impl Bundle {
+    let turret = |x: f32, y: f32| {
+        (
+            Name::new("Outpost Turret"),
+            OutpostTurret::default(),
+            Sprite {
+                image: assets.outpost_turret.clone(),
+                custom_size: Some(Vec2::splat(64.0)),
+                ..default()
+            },
+            Transform::from_xyz(x, y, 0.1),
+        )
+    };
+
+    (
+        Name::new("Outpost"),
+        Enemy,
+        OutpostAI {
+            launch: Timer::from_seconds(6.0, TimerMode::Repeating),
+        },
+        Sprite {
+            image: assets.outpost.clone(),
+            custom_size: Some(OUTPOST_SIZE),
+            ..default()
+        },
+        Transform::from_translation(position.extend(0.0)),
+        RigidBody::Static,
+        Collider::circle(OUTPOST_SIZE.x / 2.0),
+        Health(1000),
+        children![turret(-48.0, 60.0), turret(48.0, 60.0), turret(0.0, -80.0)],
+    )
+}
+
+pub fn process_outpost_ai(
+    mut commands: Commands,
+    time: Res<Time>,
+    assets: Res<EntityAssets>,
+    outposts: Query<(Entity, &Transform, &mut OutpostAI)>,
+    launched: Query<&LaunchedFrom>,
+    player: Single<&Transform, With<Player>>,
+) {
+    let player_pos = player.translation.xy();
+    for (outpost, outpost_pos, mut ai) in outposts {
+        let pos = outpost_pos.translation.xy();
+        if pos.distance(player_pos) > OUTPOST_RANGE {
+            continue;
+        }
+
+        if !ai.launch.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let alive = launched.iter().filter(|from| from.0 == outpost).count();
+        if alive >= OUTPOST_MAX_LAUNCHED {
+            continue;
+        }
+
+        // Out of the hangar on the side facing the player.
+        let bay = pos + (player_pos - pos).normalize_or_zero() * OUTPOST_SIZE.y * 0.6;
+        let rammer = EnemyMix {
+            ai: ShipType::Rammer,
+            look: ShipType::EmpireGoon,
+            weight: 1,
+            cost: 1.0,
+        };
+        let rammer = spawn_enemy_at(&mut commands, &assets, rammer, bay, player_pos);
+        commands.entity(rammer).insert(LaunchedFrom(outpost));
+    }
+}
+
+pub fn process_goon_ai(goons: Query<&mut Transform, (With<GoonAI>, Without<Dormant>)>) {
     for mut goon_pos in goons {
         goon_pos.translation.x += 1.0;
     }
@@ -329,6 +517,8 @@ pub struct EntityAssets {
     #[dependency]
     outpost: Handle<Image>,
     #[dependency]
+    outpost_turret: Handle<Image>,
+    #[dependency]
     asteroid: Handle<Image>,
     #[dependency]
     explosion: Handle<Image>,
@@ -361,6 +551,8 @@ impl FromWorld for EntityAssets {
             empire_goon: assets.load_with_settings("images/entities/Enemy1.png", make_nearest),
             pirate_ship: assets.load_with_settings("images/entities/Enemy2.png", make_nearest),
             outpost: assets.load_with_settings("images/mascot.png", make_nearest),
+            outpost_turret: assets
+                .load_with_settings("images/entities/Flagshipgun.png", make_nearest),
             asteroid: assets.load_with_settings("images/entities/Astroid 1 .png", make_nearest),
             ramming_ship: assets.load_with_settings("images/entities/Enemy3.png", make_nearest),
             explosion: assets.load_with_settings(
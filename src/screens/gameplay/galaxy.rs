@@ -0,0 +1,223 @@
+//! Lays out the planets for a run from a seed, so the same seed always gives the same
+//! galaxy and runs can be shared. The seed is shown on the pause screen and can be
+//! picked on the main menu, otherwise every run rolls a new one.
+
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+
+use rand::{
+    Rng, SeedableRng,
+    rngs::StdRng,
+    seq::{IndexedRandom, SliceRandom},
+};
+
+use super::{
+    level::PlanetType,
+    sector::{Biome, Generator, PlanetDefinition, SectorDefinition, Span},
+};
+
+/// How far past the planets and starting points the sector goes.
+const EDGE_MARGIN: f32 = 3000.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<GalaxySeed>();
+}
+
+/// The seed the player picked for the next runs, if they picked one.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct GalaxySeed(pub Option<u64>);
+
+/// The planets and biomes of the current run.
+#[derive(Resource)]
+pub struct Galaxy {
+    pub seed: u64,
+    pub planets: Vec<PlanetDefinition>,
+    pub outposts: Vec<Vec2>,
+    pub biomes: Vec<Biome>,
+    /// Where the player is allowed to go, see `bounds.rs`.
+    pub bounds: Rect,
+}
+
+impl Galaxy {
+    /// Generates the galaxy if the sector has a [`Generator`], or uses the sector's own
+    /// planets if it doesn't.
+    pub fn new(sector: &SectorDefinition, seed: u64) -> Self {
+        let Some(generator) = &sector.generator else {
+            return Self {
+                seed,
+                bounds: layout_bounds(sector, &sector.planets, &sector.outposts),
+                planets: sector.planets.clone(),
+                outposts: sector.outposts.clone(),
+                biomes: sector.biomes.clone(),
+            };
+        };
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let stops = generate_stops(generator, &mut rng);
+
+        let mut biomes = sector.biomes.clone();
+        biomes.extend(assign_regions(generator, &stops, &mut rng));
+
+        let mut outposts = sector.outposts.clone();
+        outposts.extend(place_outposts(generator, &stops, &mut rng));
+
+        let planets: Vec<_> = stops.into_iter().flatten().collect();
+        Self {
+            seed,
+            bounds: layout_bounds(sector, &planets, &outposts),
+            planets,
+            outposts,
+            biomes,
+        }
+    }
+
+    /// The first biome that contains `position`, so earlier biomes win where they overlap.
+    pub fn biome_at(&self, position: Vec2) -> Option<&Biome> {
+        self.biomes.iter().find(|biome| biome.contains(position))
+    }
+}
+
+/// The sector's size, grown to fit everything that's laid out in it with some room to
+/// spare.
+fn layout_bounds(
+    sector: &SectorDefinition,
+    planets: &[PlanetDefinition],
+    outposts: &[Vec2],
+) -> Rect {
+    let points = planets
+        .iter()
+        .map(|planet| planet.position)
+        .chain(outposts.iter().copied())
+        .chain([sector.player_start, sector.flagship_start]);
+
+    let mut bounds = Rect::from_corners(Vec2::new(0.0, -sector.size.y), sector.size);
+    for point in points {
+        bounds = bounds.union(Rect::from_center_half_size(point, Vec2::splat(EDGE_MARGIN)));
+    }
+    bounds
+}
+
+/// The planets along the path, one or two (a fork) per stop.
+fn generate_stops(generator: &Generator, rng: &mut StdRng) -> Vec<Vec<PlanetDefinition>> {
+    let planet = |planet, x, y| PlanetDefinition {
+        planet,
+        position: Vec2::new(x, y),
+        shop: true,
+    };
+    let jitter = |rng: &mut StdRng| rng.random_range(-generator.jitter..=generator.jitter);
+
+    let mut pool = PlanetPool::new(&generator.planets);
+    let mut stops = vec![vec![planet(generator.home, generator.start, jitter(rng))]];
+
+    let mut x = generator.start;
+    loop {
+        x += rng.random_range(generator.min_spacing..=generator.max_spacing);
+        // keep the last stop from crowding the goal
+        if x > generator.goal_x - generator.min_spacing {
+            break;
+        }
+        let Some(first) = pool.next(rng) else {
+            break;
+        };
+
+        if rng.random_bool(generator.fork_chance.clamp(0.0, 1.0)) {
+            let second = pool.next(rng).unwrap_or(first);
+            stops.push(vec![
+                planet(first, x, generator.fork_offset),
+                planet(second, x, -generator.fork_offset),
+            ]);
+        } else {
+            stops.push(vec![planet(first, x, jitter(rng))]);
+        }
+    }
+
+    stops.push(vec![planet(generator.goal, generator.goal_x, 0.0)]);
+    stops
+}
+
+/// Hands out planets in a random order, only reshuffling once all have been used.
+struct PlanetPool {
+    planets: Vec<PlanetType>,
+    next: usize,
+}
+
+impl PlanetPool {
+    fn new(planets: &[PlanetType]) -> Self {
+        Self {
+            planets: planets.to_vec(),
+            next: planets.len(),
+        }
+    }
+
+    fn next(&mut self, rng: &mut StdRng) -> Option<PlanetType> {
+        if self.planets.is_empty() {
+            return None;
+        }
+        if self.next == self.planets.len() {
+            self.planets.shuffle(rng);
+            self.next = 0;
+        }
+
+        self.next += 1;
+        Some(self.planets[self.next - 1])
+    }
+}
+
+/// Gives every stretch between two stops a region, never the same one twice in a row.
+/// Nothing spawns past the goal.
+fn assign_regions(
+    generator: &Generator,
+    stops: &[Vec<PlanetDefinition>],
+    rng: &mut StdRng,
+) -> Vec<Biome> {
+    if generator.regions.is_empty() {
+        return vec![];
+    }
+
+    let mut regions = vec![];
+    let mut previous = 0;
+    for (i, pair) in stops.windows(2).enumerate() {
+        let region = if i == 0 {
+            0
+        } else if generator.regions.len() > 1 {
+            // roll among all but the previous region
+            let roll = rng.random_range(0..generator.regions.len() - 1);
+            if roll >= previous { roll + 1 } else { roll }
+        } else {
+            0
+        };
+        previous = region;
+
+        let mut biome = generator.regions[region].clone();
+        biome.x = Span {
+            min: (i > 0).then_some(pair[0][0].position.x),
+            max: Some(pair[1][0].position.x),
+        };
+        regions.push(biome);
+    }
+    regions
+}
+
+/// Puts an outpost next to one planet of some of the stops in between.
+fn place_outposts(
+    generator: &Generator,
+    stops: &[Vec<PlanetDefinition>],
+    rng: &mut StdRng,
+) -> Vec<Vec2> {
+    let Some(between) = stops.get(1..stops.len().saturating_sub(1)) else {
+        return vec![];
+    };
+
+    let mut outposts = vec![];
+    for stop in between {
+        if !rng.random_bool(generator.outpost_chance.clamp(0.0, 1.0)) {
+            continue;
+        }
+        let Some(planet) = stop.choose(rng) else {
+            continue;
+        };
+        let angle = rng.random_range(0.0..TAU);
+        outposts.push(planet.position + Vec2::from_angle(angle) * generator.outpost_distance);
+    }
+    outposts
+}
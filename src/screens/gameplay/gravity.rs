@@ -0,0 +1,195 @@
+//! Planets pull on everything that moves around them: the player, enemies and shots.
+//! Diving close past a planet and out again slingshots the player, and anything that
+//! hits the surface crashes.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{AppSystems, PausableSystems, Pause};
+
+use super::{
+    GameState,
+    combat::{
+        Damage,
+        weapons::{CannonBullet, EnemyBullet},
+    },
+    culling::Dormant,
+    enemies::{Enemy, EntityAssets, FlagshipAI},
+    level::Planet,
+    player::Player,
+};
+
+/// How hard a planet pulls on things touching its surface, in units/s². Thrusters
+/// manage about 640.
+const SURFACE_GRAVITY: f32 = 300.0;
+/// How far from its center a planet pulls, in planet radii.
+const WELL_RANGE: f32 = 6.0;
+/// Passing closer than this to the surface, in planet radii, counts as a slingshot.
+const SLINGSHOT_ALTITUDE: f32 = 0.75;
+/// How much speed a slingshot adds on the way out, in units/s.
+const SLINGSHOT_BOOST: f32 = 300.0;
+/// Crashing into a planet deals this much damage per unit of impulse...
+const CRASH_DAMAGE_PER_IMPULSE: f32 = 0.1;
+/// ...but bumping into it gently doesn't hurt.
+const MIN_CRASH_DAMAGE: f32 = 10.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Slingshot>();
+
+    app.add_systems(
+        FixedUpdate,
+        (apply_gravity_wells, track_slingshot)
+            .chain()
+            .in_set(AppSystems::Update)
+            .in_set(PausableSystems)
+            .run_if(in_state(GameState::OpenWorld)),
+    );
+    // Contacts are only fresh right after a physics step, so every impact gets counted
+    // exactly once.
+    app.add_systems(
+        FixedPostUpdate,
+        process_planet_crashes
+            .after(PhysicsSet::StepSimulation)
+            .run_if(in_state(GameState::OpenWorld).and(in_state(Pause(false)))),
+    );
+}
+
+#[derive(Component, Debug, Clone, Copy)]
+pub struct GravityWell {
+    /// Where the surface is.
+    pub radius: f32,
+    pub surface_gravity: f32,
+    /// Nothing further away than this gets pulled.
+    pub range: f32,
+}
+
+impl GravityWell {
+    pub fn planet(radius: f32) -> Self {
+        Self {
+            radius,
+            surface_gravity: SURFACE_GRAVITY,
+            range: radius * WELL_RANGE,
+        }
+    }
+
+    /// The acceleration towards the well at `position`, falling off with the square of
+    /// the distance.
+    fn pull(&self, center: Vec2, position: Vec2) -> Vec2 {
+        let offset = center - position;
+        let distance = offset.length();
+        if distance > self.range || distance < f32::EPSILON {
+            return Vec2::ZERO;
+        }
+
+        offset / distance * self.surface_gravity * (self.radius / distance.max(self.radius)).powi(2)
+    }
+}
+
+/// The player's pass through a gravity well, if they're in one.
+#[derive(Resource, Default)]
+struct Slingshot {
+    planet: Option<Entity>,
+    /// The lowest they've been above the surface so far.
+    lowest: f32,
+}
+
+/// Gravity doesn't care about mass, so it changes velocities directly. That way it works
+/// on kinematic shots too.
+fn apply_gravity_wells(
+    time: Res<Time>,
+    wells: Query<(&Transform, &GravityWell)>,
+    mut bodies: Query<
+        (&Transform, &RigidBody, &mut LinearVelocity),
+        (Without<FlagshipAI>, Without<Dormant>),
+    >,
+) {
+    for (trans, body, mut velocity) in &mut bodies {
+        if body.is_static() {
+            continue;
+        }
+
+        let position = trans.translation.xy();
+        let pull: Vec2 = wells
+            .iter()
+            .map(|(well_trans, well)| well.pull(well_trans.translation.xy(), position))
+            .sum();
+        velocity.0 += pull * time.delta_secs();
+    }
+}
+
+fn track_slingshot(
+    mut slingshot: ResMut<Slingshot>,
+    wells: Query<(Entity, &Transform, &GravityWell)>,
+    player: Single<(&Transform, &mut LinearVelocity), With<Player>>,
+) {
+    let (trans, mut velocity) = player.into_inner();
+    let position = trans.translation.xy();
+
+    if let Some(planet) = slingshot.planet {
+        let Ok((_, well_trans, well)) = wells.get(planet) else {
+            slingshot.planet = None;
+            return;
+        };
+        let distance = position.distance(well_trans.translation.xy());
+        if distance < well.range {
+            slingshot.lowest = slingshot.lowest.min(distance - well.radius);
+            return;
+        }
+
+        if slingshot.lowest < well.radius * SLINGSHOT_ALTITUDE {
+            let boost = velocity.normalize_or_zero() * SLINGSHOT_BOOST;
+            velocity.0 += boost;
+        }
+        slingshot.planet = None;
+    }
+
+    for (planet, well_trans, well) in &wells {
+        let distance = position.distance(well_trans.translation.xy());
+        if distance < well.range {
+            *slingshot = Slingshot {
+                planet: Some(planet),
+                lowest: distance - well.radius,
+            };
+            return;
+        }
+    }
+}
+
+fn process_planet_crashes(
+    mut commands: Commands,
+    collisions: Collisions,
+    planets: Query<Entity, With<Planet>>,
+    player: Single<Entity, With<Player>>,
+    enemies: Query<&Transform, (With<Enemy>, Without<FlagshipAI>)>,
+    bullets: Query<(), Or<(With<CannonBullet>, With<EnemyBullet>)>>,
+    assets: Res<EntityAssets>,
+) {
+    for planet in &planets {
+        for contact_pair in collisions.collisions_with(planet) {
+            if !contact_pair.is_touching() {
+                continue;
+            }
+            let other = if contact_pair.collider1 == planet {
+                contact_pair.collider2
+            } else {
+                contact_pair.collider1
+            };
+
+            if other == *player {
+                // Scraping along the surface afterwards doesn't hurt.
+                if !contact_pair.collision_started() {
+                    continue;
+                }
+                let damage = contact_pair.max_normal_impulse().0 * CRASH_DAMAGE_PER_IMPULSE;
+                if damage >= MIN_CRASH_DAMAGE {
+                    commands.trigger_targets(Damage(damage as i32), other);
+                }
+            } else if let Ok(trans) = enemies.get(other) {
+                commands.entity(other).despawn();
+                commands.spawn((*trans, assets.get_explosion()));
+            } else if bullets.contains(other) {
+                commands.entity(other).despawn();
+            }
+        }
+    }
+}
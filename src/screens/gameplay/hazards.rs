@@ -0,0 +1,302 @@
+//! Biome hazards. While the player is in a biome with a [`Hazard`], the screen is
+//! tinted and a warning on the HUD says what it does.
+
+use std::f32::consts::TAU;
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::screens::Screen;
+
+use super::{
+    GameState, GameplayLogic,
+    combat::{Damage, Health, weapons::EField},
+    enemies::{Enemy, EntityAssets, FlagshipAI, gen_asteroid},
+    galaxy::Galaxy,
+    level::UIAssets,
+    minimap::MapFrame,
+    player::{self, Player},
+    sector::Hazard,
+};
+
+/// Solar flares burn everything this close to the player.
+const FLARE_RANGE: f32 = 1500.0;
+/// How long the screen stays bright after a flare.
+const FLARE_FLASH_SECS: f32 = 0.5;
+/// Debris is kept topped up within this range of the player, and cleaned up once it
+/// drifts out of twice that.
+const DEBRIS_RANGE: f32 = 2000.0;
+/// Close enough that new debris would pop in on screen.
+const DEBRIS_MIN_SPAWN_DISTANCE: f32 = 1200.0;
+const DEBRIS_MAX_DRIFT: f32 = 30.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<HazardState>();
+    app.add_systems(OnExit(GameState::OpenWorld), reset_hazards);
+    app.add_systems(
+        Update,
+        (
+            enter_hazards,
+            (
+                apply_nebula,
+                apply_ion_storm,
+                apply_solar_flare,
+                apply_debris,
+            ),
+        )
+            .chain()
+            .in_set(GameplayLogic),
+    );
+}
+
+#[derive(Resource, Default)]
+pub struct HazardState {
+    current: Option<Hazard>,
+    /// Counts up to the next solar flare.
+    flare: Timer,
+    /// Counts down after a flare went off.
+    flash: f32,
+}
+
+impl HazardState {
+    /// Whether the player's lasers and fields are knocked out.
+    pub fn jams_weapons(&self) -> bool {
+        self.current == Some(Hazard::IonStorm)
+    }
+}
+
+/// The tint and warning shown while in a hazard.
+#[derive(Component)]
+struct HazardHud;
+
+#[derive(Component)]
+struct HazardWarning;
+
+/// Asteroids spawned by a [`Hazard::Debris`] field.
+#[derive(Component)]
+struct Debris;
+
+fn reset_hazards(mut state: ResMut<HazardState>) {
+    *state = HazardState::default();
+}
+
+pub(super) fn tint(hazard: Hazard) -> Color {
+    match hazard {
+        Hazard::Nebula { .. } => Color::srgba(0.5, 0.2, 0.6, 0.25),
+        Hazard::IonStorm => Color::srgba(0.2, 0.5, 1.0, 0.15),
+        Hazard::SolarFlare { .. } => Color::srgba(1.0, 0.6, 0.1, 0.1),
+        Hazard::Debris { .. } => Color::srgba(0.4, 0.35, 0.3, 0.1),
+    }
+}
+
+fn warning(hazard: Hazard) -> &'static str {
+    match hazard {
+        Hazard::Nebula { .. } => "NEBULA - sensors down",
+        Hazard::IonStorm => "ION STORM - lasers and fields offline",
+        Hazard::SolarFlare { .. } => "SOLAR FLARE ACTIVITY",
+        Hazard::Debris { .. } => "DEBRIS FIELD",
+    }
+}
+
+/// Swaps the HUD over when the player flies into a different hazard, or out of one.
+fn enter_hazards(
+    mut commands: Commands,
+    mut state: ResMut<HazardState>,
+    galaxy: Res<Galaxy>,
+    ui_assets: Res<UIAssets>,
+    player: Single<&Transform, With<Player>>,
+    huds: Query<Entity, With<HazardHud>>,
+) {
+    let hazard = galaxy
+        .biome_at(player.translation.xy())
+        .and_then(|biome| biome.hazard);
+    if hazard == state.current {
+        return;
+    }
+
+    state.current = hazard;
+    state.flash = 0.0;
+    for hud in &huds {
+        commands.entity(hud).despawn();
+    }
+
+    let Some(hazard) = hazard else {
+        return;
+    };
+    if let Hazard::SolarFlare { interval, .. } = hazard {
+        state.flare = Timer::from_seconds(interval, TimerMode::Repeating);
+    }
+
+    commands.spawn((
+        Name::new("Hazard HUD"),
+        HazardHud,
+        StateScoped(GameState::OpenWorld),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            padding: UiRect::top(Val::Percent(16.0)),
+            ..default()
+        },
+        BackgroundColor(tint(hazard)),
+        Pickable::IGNORE,
+        children![(
+            HazardWarning,
+            Text::new(warning(hazard)),
+            TextFont {
+                font: ui_assets.font.clone(),
+                font_size: 28.0,
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.4, 0.3)),
+        )],
+    ));
+}
+
+fn apply_nebula(
+    state: Res<HazardState>,
+    mut player: Single<&mut LinearDamping, With<Player>>,
+    mut maps: Query<&mut Visibility, With<MapFrame>>,
+) {
+    let extra = match state.current {
+        Some(Hazard::Nebula { damping }) => damping,
+        _ => 0.0,
+    };
+
+    player.0 = player::LINEAR_DAMPING + extra;
+    for mut visibility in &mut maps {
+        *visibility = if extra > 0.0 {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+/// Fields go dark and quiet in an ion storm. Lasers check [`HazardState::jams_weapons`]
+/// themselves. Fields are only touched when the storm comes or goes, or when they (or
+/// their sound) are new.
+fn apply_ion_storm(
+    mut commands: Commands,
+    state: Res<HazardState>,
+    mut was_jammed: Local<bool>,
+    mut fields: Query<(Entity, Ref<EField>, &mut Visibility, Option<Ref<AudioSink>>)>,
+) {
+    let jammed = state.jams_weapons();
+    let changed = jammed != *was_jammed;
+    *was_jammed = jammed;
+
+    for (field, efield, mut visibility, sink) in &mut fields {
+        let sink_added = sink.as_ref().is_some_and(|sink| sink.is_added());
+        if !changed && !efield.is_added() && !sink_added {
+            continue;
+        }
+
+        if jammed {
+            commands.entity(field).insert(ColliderDisabled);
+            *visibility = Visibility::Hidden;
+            sink.inspect(|sink| sink.pause());
+        } else {
+            commands.entity(field).remove::<ColliderDisabled>();
+            *visibility = Visibility::Inherited;
+            sink.inspect(|sink| sink.play());
+        }
+    }
+}
+
+fn apply_solar_flare(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut state: ResMut<HazardState>,
+    player: Single<(Entity, &Transform), With<Player>>,
+    targets: Query<(Entity, &Transform), (With<Health>, With<Enemy>, Without<FlagshipAI>)>,
+    mut hud: Query<&mut BackgroundColor, With<HazardHud>>,
+    mut warning_text: Query<&mut Text, With<HazardWarning>>,
+) {
+    let Some(
+        hazard @ Hazard::SolarFlare {
+            warning: warning_secs,
+            damage,
+            ..
+        },
+    ) = state.current
+    else {
+        return;
+    };
+    let (player, player_trans) = player.into_inner();
+
+    state.flash = (state.flash - time.delta_secs()).max(0.0);
+    if state.flare.tick(time.delta()).just_finished() {
+        state.flash = FLARE_FLASH_SECS;
+        commands.trigger_targets(Damage(damage), player);
+        for (target, trans) in &targets {
+            if trans.translation.distance(player_trans.translation) < FLARE_RANGE {
+                commands.trigger_targets(Damage(damage), target);
+            }
+        }
+    }
+
+    let remaining = state.flare.remaining_secs();
+    let base = tint(hazard);
+    let alpha = if state.flash > 0.0 {
+        0.8 * state.flash / FLARE_FLASH_SECS
+    } else if remaining < warning_secs {
+        // pulses twice a second
+        base.alpha() + 0.2 * (0.5 + 0.5 * (remaining * TAU * 2.0).cos())
+    } else {
+        base.alpha()
+    };
+    for mut color in &mut hud {
+        color.0 = base.with_alpha(alpha);
+    }
+    for mut text in &mut warning_text {
+        text.0 = if remaining < warning_secs {
+            format!("SOLAR FLARE IN {}", remaining.ceil())
+        } else {
+            warning(hazard).to_string()
+        };
+    }
+}
+
+fn apply_debris(
+    mut commands: Commands,
+    state: Res<HazardState>,
+    entity_assets: Res<EntityAssets>,
+    player: Single<&Transform, With<Player>>,
+    debris: Query<(Entity, &Transform), With<Debris>>,
+) {
+    let player_pos = player.translation.xy();
+    let mut nearby = 0;
+    for (ent, trans) in &debris {
+        let distance = trans.translation.xy().distance(player_pos);
+        if distance > DEBRIS_RANGE * 2.0 {
+            commands.entity(ent).despawn();
+        } else if distance < DEBRIS_RANGE {
+            nearby += 1;
+        }
+    }
+
+    let Some(Hazard::Debris { count }) = state.current else {
+        return;
+    };
+    let mut rng = rand::rng();
+    for _ in nearby..count {
+        let angle = rng.random_range(0.0..TAU);
+        let distance = rng.random_range(DEBRIS_MIN_SPAWN_DISTANCE..DEBRIS_RANGE);
+        let drift =
+            Vec2::from_angle(rng.random_range(0.0..TAU)) * rng.random_range(0.0..DEBRIS_MAX_DRIFT);
+
+        commands.spawn((
+            Name::new("Debris"),
+            Debris,
+            StateScoped(Screen::Gameplay),
+            gen_asteroid(
+                &entity_assets,
+                player_pos + Vec2::from_angle(angle) * distance,
+                drift,
+            ),
+        ));
+    }
+}
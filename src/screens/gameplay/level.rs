@@ -2,6 +2,7 @@
 
 use avian2d::prelude::*;
 use rand::Rng;
+use serde::Deserialize;
 
 use bevy::{color::palettes::css::GREEN, ecs::entity, prelude::*};
 
@@ -10,7 +11,10 @@ use crate::{
     menus::Menu,
     screens::{
         gameplay::{
-            enemies::{gen_asteroid, gen_flagship, gen_goon, gen_rammer, EntityAssets},
+            enemies::{
+                gen_asteroid, gen_flagship, gen_goon, gen_outpost, gen_pirate, gen_rammer,
+                EntityAssets,
+            },
             upgrade_menu::generate_buy_menu,
         },
         Screen,
@@ -18,9 +22,18 @@ use crate::{
 };
 
 use super::{
-    combat::{weapons::EvilLaser, Damage, Health},
-    enemies::{FlagshipAI, RammerAI, ShipType},
+    combat::{
+        weapons::{EnemyBullet, EvilLaser},
+        Damage, Health,
+    },
+    culling::Cull,
+    enemies::{RammerAI, ShipType},
+    galaxy::{Galaxy, GalaxySeed},
+    gravity::GravityWell,
+    minimap::MapFrame,
+    parallax::parallax_layer,
     player::{gen_player, Player, PlayerAssets},
+    sector::{EnemyMix, SectorDefinition},
     upgrade_menu::{UpgradeTypes, Upgrades},
     GameplayLogic,
 };
@@ -35,22 +48,16 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(Update, world_update.in_set(GameplayLogic));
 }
 
-const LVL1X: f32 = 4000.0;
-const LVL2X: f32 = 21000.0;
-const LVL3X: f32 = 35000.0;
-const LVL4X: f32 = 50000.0;
-const LVL5X: f32 = 62000.0;
-const LVL6X: f32 = 75000.0;
-const LVL7X: f32 = 100000.0;
-const YMAX: f32 = 15000.0;
+/// Planets are solid, see `gravity.rs`.
+pub const PLANET_RADIUS: f32 = 256.0;
+/// How close to the surface the player has to get to land at a planet's shop.
+const DOCKING_RANGE: f32 = 200.0;
 
 #[derive(Resource, Asset, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct LevelAssets {
     #[dependency]
-    music: Handle<AudioSource>,
-    #[dependency]
-    background: Handle<Image>,
+    pub sector: Handle<SectorDefinition>,
     #[dependency]
     planet1: Handle<Image>,
     #[dependency]
@@ -110,9 +117,7 @@ impl FromWorld for LevelAssets {
         use crate::util::make_nearest;
         let assets = world.resource::<AssetServer>();
         Self {
-            music: assets.load("audio/music/Orbital fight.ogg"),
-            background: assets
-                .load_with_settings("images/level/T_BackgroundTiling.png", make_nearest),
+            sector: assets.load("sectors/vampire.sector.ron"),
             planet1: assets.load_with_settings("images/level/Planet1.png", make_nearest),
             planet2: assets.load_with_settings("images/level/Planet2.png", make_nearest),
             planet3: assets.load_with_settings("images/level/planet3.png", make_nearest),
@@ -139,46 +144,45 @@ pub struct VisistedPlanet(pub PlanetType);
 #[reflect(Component)]
 pub struct MainOST;
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
-#[reflect(Component)]
-pub struct BackgroundAccess;
-/// A system that spawns the main level.
+/// A system that spawns the main level, as laid out in the [`SectorDefinition`].
 pub fn spawn_level(
     mut commands: Commands,
     level_assets: Res<LevelAssets>,
     player_assets: Res<PlayerAssets>,
     entity_assets: Res<EntityAssets>,
     ui_assets: Res<UIAssets>,
+    sectors: Res<Assets<SectorDefinition>>,
+    seed: Res<GalaxySeed>,
 ) {
-    commands.spawn((
-        Name::new("Background"),
-        StateScoped(Screen::Gameplay),
-        Transform::from_xyz(0.0, 0.0, -1.0),
-        BackgroundAccess,
-        Sprite {
-            image: level_assets.background.clone(),
-            custom_size: Some(Vec2 {
-                x: LVL7X / 10.0 * 16.0,
-                y: LVL7X / 10.0 * 9.0,
-            }),
-            image_mode: SpriteImageMode::Tiled {
-                tile_x: true,
-                tile_y: true,
-                stretch_value: 2.0,
-            },
-            ..default()
-        },
-    ));
+    let Some(sector) = sectors.get(&level_assets.sector) else {
+        log::error!("sector definition isn't loaded");
+        return;
+    };
+    let galaxy = Galaxy::new(sector, seed.unwrap_or_else(rand::random));
+    log::info!("generated {} with seed {:X}", sector.name, galaxy.seed);
+    for biome in &galaxy.biomes {
+        log::debug!("biome `{}` at x {:?}, y {:?}", biome.name, biome.x, biome.y);
+    }
+
+    for layer in &sector.background {
+        commands.spawn((parallax_layer(layer), StateScoped(Screen::Gameplay)));
+    }
 
     commands.spawn((
-        VisistedPlanet(PlanetType::LavaPlanet),
+        VisistedPlanet(
+            galaxy
+                .planets
+                .first()
+                .map(|planet| planet.planet)
+                .unwrap_or_default(),
+        ),
         StateScoped(Screen::Gameplay),
     ));
 
     //Spawn music
     commands.spawn((
         MainOST,
-        AudioPlayer::new(level_assets.music.clone()),
+        AudioPlayer::new(sector.music.clone()),
         PlaybackSettings {
             mode: bevy::audio::PlaybackMode::Loop,
             ..default()
@@ -192,83 +196,25 @@ pub fn spawn_level(
             Transform::from_xyz(0.0, 0.0, 0.0),
             Visibility::default(),
             StateScoped(Screen::Gameplay),
-            children![
-                gen_player(400.0, &player_assets),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL1X * 0.1, 128.0),
-                    PlanetType::LavaPlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL2X * 0.1, YMAX / 25.0),
-                    PlanetType::GreenPlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL2X * 0.1, -YMAX / 25.0),
-                    PlanetType::DesertPlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL3X * 0.1, 0.0),
-                    PlanetType::SpaceStation,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL4X * 0.1, YMAX / 25.0),
-                    PlanetType::WaterPlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL4X * 0.1, -YMAX / 25.0),
-                    PlanetType::IcePlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL5X * 0.1, 0.0),
-                    PlanetType::HollowPlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL6X * 0.1, YMAX / 25.0),
-                    PlanetType::GrayPlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(LVL6X * 0.1, -YMAX / 25.0),
-                    PlanetType::PurplePlanet,
-                    false
-                ),
-                gen_planet(
-                    &level_assets,
-                    &ui_assets,
-                    Vec2::new(95000.0 * 0.1, 0.0),
-                    PlanetType::EarthPlanet,
-                    false
-                ),
-            ],
+            children![gen_player(400.0, sector.player_start, &player_assets)],
         ))
         .with_children(|level| {
+            for planet in &galaxy.planets {
+                level.spawn(gen_planet(
+                    &level_assets,
+                    &ui_assets,
+                    planet.position,
+                    planet.planet,
+                    planet.shop,
+                ));
+            }
+
+            for &outpost in &galaxy.outposts {
+                level.spawn(gen_outpost(&entity_assets, outpost));
+            }
+
             level
-                .spawn(gen_flagship(&entity_assets))
+                .spawn(gen_flagship(&entity_assets, sector.flagship_start))
                 .with_children(|flagship| {
                     flagship.spawn((
                         Transform::from_translation(Vec3::new(0.0, 16.0, 0.0)),
@@ -282,10 +228,11 @@ pub fn spawn_level(
         });
 
     commands.spawn(gen_ui(&ui_assets));
+    commands.insert_resource(galaxy);
 }
 
 #[repr(usize)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Default, Reflect)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Default, Reflect, Deserialize)]
 pub enum PlanetType {
     #[default]
     LavaPlanet,
@@ -305,9 +252,10 @@ pub struct ShopMarker;
 
 #[derive(Component)]
 pub struct Planet {
-    pub x: f32,
-    pub y: f32,
     pub has_shopped: bool,
+    /// Whether the player has docked here. Planets without a shop start out
+    /// `has_shopped`, but never get visited.
+    pub visited: bool,
     pub planet_type: PlanetType,
 }
 
@@ -316,13 +264,12 @@ pub fn gen_planet(
     ui_assets: &UIAssets,
     position: Vec2,
     planet_name: PlanetType,
-    first_planet: bool,
+    has_shop: bool,
 ) -> impl Bundle {
     (
         Planet {
-            x: position.x,
-            y: position.y,
-            has_shopped: first_planet,
+            has_shopped: !has_shop,
+            visited: false,
             planet_type: planet_name,
         },
         Sprite {
@@ -338,13 +285,15 @@ pub fn gen_planet(
                 PlanetType::HollowPlanet => assets.planet9.clone(),
                 PlanetType::IcePlanet => assets.planet10.clone(),
             },
-            custom_size: Some(Vec2 { x: 512.0, y: 512.0 }),
+            custom_size: Some(Vec2::splat(PLANET_RADIUS * 2.0)),
             ..default()
         },
-        //RigidBody::Static,
-        //Collider::circle(256.0),
+        RigidBody::Static,
+        Collider::circle(PLANET_RADIUS),
+        Restitution::new(0.5),
+        GravityWell::planet(PLANET_RADIUS),
         Transform::from_xyz(position.x, position.y, -0.5),
-        if !first_planet {
+        if has_shop {
             children![
                 (
                     ShopMarker,
@@ -409,14 +358,6 @@ pub struct HPBarAnti;
 #[reflect(Component)]
 pub struct MiniMap;
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
-#[reflect(Component)]
-pub struct MiniMapRed;
-
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
-#[reflect(Component)]
-pub struct MiniMapPos;
-
 pub fn gen_ui(ui_assets: &Res<UIAssets>) -> impl Bundle {
     (
         Name::new("UIBox"),
@@ -467,10 +408,12 @@ pub fn gen_ui(ui_assets: &Res<UIAssets>) -> impl Bundle {
             (
                 //Mini-map
                 MiniMap,
+                MapFrame::Mini,
                 Node {
                     width: Val::Percent(40.0),
                     height: Val::Percent(60.0),
                     left: Val::Percent(6.0),
+                    overflow: Overflow::clip(),
                     ..default()
                 },
                 ImageNode {
@@ -478,26 +421,6 @@ pub fn gen_ui(ui_assets: &Res<UIAssets>) -> impl Bundle {
                     ..default()
                 },
                 BackgroundColor(Color::srgb(0.3, 0.2, 0.3)),
-                children![
-                    (
-                        MiniMapRed,
-                        Node {
-                            width: Val::Percent(40.0),
-                            height: Val::Percent(100.0),
-                            ..default()
-                        },
-                        BackgroundColor(Color::srgba(1.0, 0.0, 0.0, 0.5)),
-                    ),
-                    (
-                        MiniMapPos,
-                        Node {
-                            width: Val::Px(8.0),
-                            height: Val::Px(8.0),
-                            ..default()
-                        },
-                        BackgroundColor(Color::srgba(0.05, 1.0, 0.05, 0.9)),
-                    )
-                ]
             ),
             (
                 //Spawns big button??
@@ -526,56 +449,12 @@ pub fn gen_ui(ui_assets: &Res<UIAssets>) -> impl Bundle {
 
 pub fn world_update(
     mut commands: Commands,
-    entity_assets: Res<EntityAssets>,
     mut gizmo: Gizmos,
     player: Single<(&Transform, &Health, &Upgrades), With<Player>>,
-    flagship: Single<&Transform, With<FlagshipAI>>,
     mut ui_position: Single<&mut Text, With<UIPosition>>,
-    mut hp_bar: Single<
-        &mut Node,
-        (
-            With<HPBar>,
-            Without<HPBarAnti>,
-            Without<MiniMapRed>,
-            Without<MiniMapPos>,
-        ),
-    >,
-    mut anti_hp_bar: Single<
-        &mut Node,
-        (
-            With<HPBarAnti>,
-            Without<HPBar>,
-            Without<MiniMapRed>,
-            Without<MiniMapPos>,
-        ),
-    >,
-    mut mini_map_enemy: Single<
-        &mut Node,
-        (
-            With<MiniMapRed>,
-            Without<HPBarAnti>,
-            Without<HPBar>,
-            Without<MiniMapPos>,
-        ),
-    >,
-    mut mini_map_pos: Single<
-        &mut Node,
-        (
-            With<MiniMapPos>,
-            Without<HPBarAnti>,
-            Without<HPBar>,
-            Without<MiniMapRed>,
-        ),
-    >,
-    planets: Query<
-        (&Transform, &mut Planet, Entity),
-        (
-            Without<HPBarAnti>,
-            Without<HPBar>,
-            Without<MiniMapRed>,
-            Without<MiniMapPos>,
-        ),
-    >, //For shop checking
+    mut hp_bar: Single<&mut Node, (With<HPBar>, Without<HPBarAnti>)>,
+    mut anti_hp_bar: Single<&mut Node, (With<HPBarAnti>, Without<HPBar>)>,
+    planets: Query<(&Transform, &mut Planet, Entity), (Without<HPBarAnti>, Without<HPBar>)>, //For shop checking
     mut next_planet: Single<&mut VisistedPlanet>,
     mut next_menu: ResMut<NextState<Menu>>,
 ) {
@@ -600,208 +479,146 @@ pub fn world_update(
     //Planet collision
     for (planet_transform, mut planet, entity) in planets {
         if !planet.has_shopped
-            && (player.translation - planet_transform.translation).length() < 200.0
+            && (player.translation - planet_transform.translation).length()
+                < PLANET_RADIUS + DOCKING_RANGE
         {
             planet.has_shopped = true;
+            planet.visited = true;
             next_planet.0 = planet.planet_type;
             commands.entity(entity).despawn_related::<Children>();
             next_menu.set(Menu::Buy);
         }
     }
 
-    //Mini-map
-    let redzone = flagship.translation.x / LVL7X * 100.0;
-    mini_map_enemy.width = Val::Percent(redzone);
-    mini_map_pos.left = Val::Percent(player.translation.x / LVL7X * 100.0 - redzone);
-    mini_map_pos.top = Val::Percent(45.0 - player.translation.y / YMAX * 100.0);
-
     //mini_map[0].Node.width = Val::Percent(10.0);
-
-    //Enemy spawning depending on biome
-
-    if player.translation.y > YMAX / 2.0 {
-        spawn_enemy(
-            commands,
-            entity_assets,
-            5,
-            ShipType::Asteroid,
-            ShipType::Asteroid,
-            player.translation,
-            SpawnPatterns::Top,
-        );
-    } else if player.translation.y < -YMAX / 2.0 {
-        spawn_enemy(
-            commands,
-            entity_assets,
-            5,
-            ShipType::Asteroid,
-            ShipType::Asteroid,
-            player.translation,
-            SpawnPatterns::Bot,
-        );
-    } else if player.translation.x < LVL2X {
-        spawn_enemy(
-            commands,
-            entity_assets,
-            15,
-            ShipType::Rammer,
-            ShipType::EmpireGoon,
-            player.translation,
-            SpawnPatterns::Circle,
-        );
-    } else if player.translation.x < LVL3X {
-        spawn_enemy(
-            commands,
-            entity_assets,
-            10,
-            ShipType::Rammer,
-            ShipType::PirateShip,
-            player.translation,
-            SpawnPatterns::Circle,
-        );
-    } else if player.translation.x < LVL4X {
-        spawn_enemy(
-            commands,
-            entity_assets,
-            5,
-            ShipType::Asteroid,
-            ShipType::Asteroid,
-            player.translation,
-            SpawnPatterns::Circle,
-        );
-    } else if player.translation.x < LVL5X {
-        spawn_enemy(
-            commands,
-            entity_assets,
-            5,
-            ShipType::Rammer,
-            ShipType::Asteroid,
-            player.translation,
-            SpawnPatterns::Right,
-        );
-    } else if player.translation.x < LVL6X {
-        spawn_enemy(
-            commands,
-            entity_assets,
-            5,
-            ShipType::Rammer,
-            ShipType::EmpireGoon,
-            player.translation,
-            SpawnPatterns::Top,
-        );
-    }
-}
-
-#[repr(usize)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
-pub enum SpawnPatterns {
-    Circle,
-    Top,
-    Bot,
-    Right,
 }
 
+/// Spawns one enemy around the player, `angle` radians clockwise from straight up (see
+/// [`SpawnPattern::angle`](super::sector::SpawnPattern::angle)).
 pub fn spawn_enemy(
-    mut commands: Commands,
-    entity_assets: Res<EntityAssets>,
-    spawnrate: usize,
-    ship_type_for_ai: ShipType,
-    ship_type_for_look: ShipType,
+    commands: &mut Commands,
+    entity_assets: &EntityAssets,
+    enemy: EnemyMix,
+    angle: f32,
     player_pos: Vec3,
-    spawn_patter: SpawnPatterns,
-) {
-    let mut rng = rand::thread_rng();
-    if 0 == rng.gen_range(0..spawnrate) {
-        let rand_angle = match spawn_patter {
-            SpawnPatterns::Circle => (rng.gen_range(0..360) as f32 / 180.0 * 3.14) as f32,
-            SpawnPatterns::Bot => (rng.gen_range(135..225) as f32 / 180.0 * 3.14) as f32,
-            SpawnPatterns::Top => (rng.gen_range(135..225) as f32 / 180.0 * 3.14 + 135.0) as f32,
-            SpawnPatterns::Right => (rng.gen_range(45..135) as f32 / 180.0 * 3.14) as f32,
-            _ => (rng.gen_range(45..135) as f32 / 180.0 * 3.14) as f32,
-        };
-
-        let relative_postion = Vec2::new(rand_angle.sin(), rand_angle.cos()) * 900.0;
-        let position = Vec2::new(player_pos.x, player_pos.y) + relative_postion;
-
-        let rand_deviation =
-            Vec2::new(rng.gen_range(-10..10) as f32, rng.gen_range(-10..10) as f32) / 20.0;
-        let rand_speed = (rng.gen_range(100..300) as f32) / 1500.0;
-
-        match ship_type_for_ai {
-            ShipType::Asteroid => {
-                commands.spawn((
-                    Name::new("Asteroid"),
+) -> Entity {
+    let position = player_pos.xy() + Vec2::new(angle.sin(), angle.cos()) * 900.0;
+    spawn_enemy_at(commands, entity_assets, enemy, position, player_pos.xy())
+}
+
+/// Spawns one enemy at `position`, heading for `target`.
+pub fn spawn_enemy_at(
+    commands: &mut Commands,
+    entity_assets: &EntityAssets,
+    enemy: EnemyMix,
+    position: Vec2,
+    target: Vec2,
+) -> Entity {
+    let mut rng = rand::rng();
+    let (ship_type_for_ai, ship_type_for_look) = (enemy.ai, enemy.look);
+
+    let relative_postion = position - target;
+
+    let rand_deviation = Vec2::new(
+        rng.random_range(-10..10) as f32,
+        rng.random_range(-10..10) as f32,
+    ) / 20.0;
+    let rand_speed = (rng.random_range(100..300) as f32) / 1500.0;
+
+    match ship_type_for_ai {
+        ShipType::Asteroid => commands
+            .spawn((
+                Name::new("Asteroid"),
+                StateScoped(Screen::Gameplay),
+                Cull,
+                gen_asteroid(
+                    entity_assets,
+                    position,
+                    -(relative_postion + rand_deviation) * rand_speed,
+                ),
+            ))
+            .id(),
+        ShipType::EmpireGoon => commands
+            .spawn((
+                Name::new("Rammer_goon"),
+                StateScoped(Screen::Gameplay),
+                Cull,
+                gen_rammer(
+                    entity_assets,
+                    position,
+                    Vec2::ZERO,
+                    ship_type_for_look,
+                    -3.14 / 2.0,
+                ),
+            ))
+            .observe(
+                |trigger: Trigger<OnCollisionStart>,
+                 mut commands: Commands,
+                 trans: Query<&Transform, With<RammerAI>>,
+                 enemy_bullets: Query<(), With<EnemyBullet>>,
+                 assets: Res<EntityAssets>| {
+                    // shots from turrets and pirates fly straight through
+                    if enemy_bullets.contains(trigger.collider) {
+                        return;
+                    }
+                    commands.trigger_targets(Damage(30), trigger.collider);
+                    commands.get_entity(trigger.target()).unwrap().despawn();
+                    commands.spawn((
+                        trans.get(trigger.target()).unwrap().clone(),
+                        assets.get_explosion(),
+                    ));
+                },
+            )
+            .id(),
+        ShipType::Rammer => {
+            commands
+                .spawn((
+                    Name::new("Rammer"),
                     StateScoped(Screen::Gameplay),
-                    gen_asteroid(
-                        &entity_assets,
+                    Cull,
+                    gen_rammer(
+                        entity_assets,
                         position,
-                        -(relative_postion + rand_deviation) * rand_speed,
+                        Vec2::ZERO,
+                        ship_type_for_look,
+                        relative_postion.to_angle() + 3.14 / 2.0,
                     ),
-                ));
-            }
-            ShipType::EmpireGoon => {
-                commands
-                    .spawn((
-                        Name::new("Rammer_goon"),
-                        StateScoped(Screen::Gameplay),
-                        gen_rammer(
-                            &entity_assets,
-                            position,
-                            Vec2::ZERO,
-                            ship_type_for_look,
-                            -3.14 / 2.0,
-                        ),
-                    ))
-                    .observe(
-                        |trigger: Trigger<OnCollisionStart>,
-                         mut commands: Commands,
-                         trans: Query<&Transform, With<RammerAI>>,
-                         player: Single<Entity, With<Player>>,
-                         assets: Res<EntityAssets>| {
-                            commands.trigger_targets(Damage(30), trigger.collider);
-                            commands.get_entity(trigger.target()).unwrap().despawn();
-                            commands.spawn((
-                                trans.get(trigger.target()).unwrap().clone(),
-                                assets.get_explosion(),
-                            ));
-                        },
-                    );
-            }
-            ShipType::Rammer => {
-                commands
-                    .spawn((
-                        Name::new("Rammer"),
-                        StateScoped(Screen::Gameplay),
-                        gen_rammer(
-                            &entity_assets,
-                            position,
-                            Vec2::ZERO,
-                            ship_type_for_look,
-                            relative_postion.to_angle() + 3.14 / 2.0,
-                        ),
-                    ))
-                    .observe(
-                        |trigger: Trigger<OnCollisionStart>,
-                         mut commands: Commands,
-                         trans: Query<&Transform, With<RammerAI>>,
-                         player: Single<Entity, With<Player>>,
-                         assets: Res<EntityAssets>| {
-                            commands.trigger_targets(Damage(30), trigger.collider);
-                            //commands.get_entity(trigger.target()).unwrap().despawn();
-                            commands.spawn((
-                                trans.get(trigger.target()).unwrap().clone(),
-                                assets.get_explosion(),
-                            ));
-                        },
-                    );
-            }
-            _ => {
-                commands.spawn((
-                    Name::new("???"),
-                    StateScoped(Screen::Gameplay),
-                    gen_goon(&entity_assets, position),
-                ));
-            }
-        };
+                ))
+                .observe(
+                    |trigger: Trigger<OnCollisionStart>,
+                     mut commands: Commands,
+                     trans: Query<&Transform, With<RammerAI>>,
+                     enemy_bullets: Query<(), With<EnemyBullet>>,
+                     assets: Res<EntityAssets>| {
+                        // shots from turrets and pirates fly straight through
+                        if enemy_bullets.contains(trigger.collider) {
+                            return;
+                        }
+                        commands.trigger_targets(Damage(30), trigger.collider);
+                        //commands.get_entity(trigger.target()).unwrap().despawn();
+                        commands.spawn((
+                            trans.get(trigger.target()).unwrap().clone(),
+                            assets.get_explosion(),
+                        ));
+                    },
+                )
+                .id()
+        }
+        ShipType::PirateShip => commands
+            .spawn((
+                Name::new("Pirate"),
+                StateScoped(Screen::Gameplay),
+                Cull,
+                gen_pirate(entity_assets, position),
+            ))
+            .id(),
+        _ => commands
+            .spawn((
+                Name::new("???"),
+                StateScoped(Screen::Gameplay),
+                Cull,
+                gen_goon(entity_assets, position),
+            ))
+            .id(),
     }
 }
@@ -0,0 +1,334 @@
+//! The minimap on the HUD and the full sector map. Both show the sector's edge, the
+//! biomes with hazards, every planet and outpost, the flagship, the player and the
+//! enemies close to them. Press Z to zoom the minimap in and out, and M for the full map.
+
+use std::collections::HashMap;
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use super::{
+    GameState, GameplayLogic,
+    enemies::{Enemy, FlagshipAI, OutpostAI},
+    galaxy::Galaxy,
+    hazards,
+    level::{Planet, UIAssets},
+    player::Player,
+};
+
+const ZOOM_KEY: KeyCode = KeyCode::KeyZ;
+const MAP_KEY: KeyCode = KeyCode::KeyM;
+/// How much of the sector the minimap shows across, in world units. `None` is all of
+/// it.
+const ZOOM_LEVELS: [Option<f32>; 3] = [None, Some(30000.0), Some(10000.0)];
+/// How much room the map leaves around the sector's edge.
+const MARGIN: f32 = 0.05;
+/// Enemies further than this from the player don't show up.
+const BLIP_RANGE: f32 = 6000.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<MapZoom>();
+    app.add_systems(OnExit(GameState::OpenWorld), reset_zoom);
+    app.add_systems(
+        Update,
+        (
+            cycle_zoom.run_if(input_just_pressed(ZOOM_KEY)),
+            toggle_full_map.run_if(input_just_pressed(MAP_KEY)),
+            add_zones,
+            (update_zones, update_markers),
+        )
+            .chain()
+            .in_set(GameplayLogic),
+    );
+}
+
+/// Index into [`ZOOM_LEVELS`] for the minimap.
+#[derive(Resource, Default)]
+struct MapZoom(usize);
+
+/// A map, either the minimap on the HUD or the full sector map.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MapFrame {
+    Mini,
+    Full,
+}
+
+/// Shows where the entity is on its map.
+#[derive(Component)]
+struct MapMarker(Entity);
+
+/// An area of the sector drawn onto its map, in world units.
+#[derive(Component)]
+struct MapZone(Rect);
+
+/// What a marker stands for, which decides how it looks.
+#[derive(Clone, Copy)]
+enum Blip {
+    Player,
+    Flagship,
+    /// Always shown, so the player can plan around them.
+    Outpost,
+    /// A planet whose shop is still open.
+    Shop,
+    /// A planet without a shop, or one that's been visited.
+    Planet,
+    Enemy,
+}
+
+impl Blip {
+    fn size(self) -> f32 {
+        match self {
+            Blip::Flagship | Blip::Outpost => 12.0,
+            Blip::Shop | Blip::Planet => 10.0,
+            Blip::Player => 8.0,
+            Blip::Enemy => 4.0,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            Blip::Player => Color::srgba(0.05, 1.0, 0.05, 0.9),
+            Blip::Flagship => Color::srgb(1.0, 0.1, 0.1),
+            Blip::Outpost => Color::srgb(0.8, 0.2, 0.8),
+            Blip::Shop => Color::srgb(1.0, 0.85, 0.2),
+            Blip::Planet => Color::srgb(0.5, 0.5, 0.6),
+            Blip::Enemy => Color::srgb(1.0, 0.5, 0.2),
+        }
+    }
+
+    /// The player and flagship are drawn over everything else.
+    fn z_index(self) -> ZIndex {
+        match self {
+            Blip::Player | Blip::Flagship => ZIndex(1),
+            _ => ZIndex(0),
+        }
+    }
+}
+
+fn reset_zoom(mut zoom: ResMut<MapZoom>) {
+    *zoom = MapZoom::default();
+}
+
+/// The part of the world a map shows.
+fn map_area(frame: MapFrame, zoom: &MapZoom, bounds: Rect, player: Vec2) -> Rect {
+    let sector = bounds.inflate(bounds.size().max_element() * MARGIN);
+    match (frame, ZOOM_LEVELS[zoom.0]) {
+        (MapFrame::Mini, Some(width)) => {
+            let aspect = sector.height() / sector.width();
+            Rect::from_center_size(player, Vec2::new(width, width * aspect))
+        }
+        _ => sector,
+    }
+}
+
+/// Where `position` is on a map showing `area`, as percentages from its top left
+/// corner.
+fn map_percent(area: Rect, position: Vec2) -> Vec2 {
+    Vec2::new(
+        (position.x - area.min.x) / area.width(),
+        (area.max.y - position.y) / area.height(),
+    ) * 100.0
+}
+
+fn cycle_zoom(mut zoom: ResMut<MapZoom>) {
+    zoom.0 = (zoom.0 + 1) % ZOOM_LEVELS.len();
+}
+
+fn toggle_full_map(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    frames: Query<(Entity, &MapFrame)>,
+) {
+    if let Some((map, _)) = frames.iter().find(|(_, frame)| **frame == MapFrame::Full) {
+        commands.entity(map).despawn();
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Sector Map"),
+        MapFrame::Full,
+        StateScoped(GameState::OpenWorld),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(10.0),
+            top: Val::Percent(5.0),
+            width: Val::Percent(80.0),
+            height: Val::Percent(75.0),
+            border: UiRect::all(Val::Px(2.0)),
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        ImageNode {
+            image: ui_assets.mini_map.clone(),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.3, 0.2, 0.3)),
+        BorderColor(Color::srgb(0.7, 0.7, 0.9)),
+        ZIndex(3),
+        Pickable::IGNORE,
+    ));
+}
+
+/// Draws the sector's edge and its hazards onto new maps.
+fn add_zones(mut commands: Commands, galaxy: Res<Galaxy>, frames: Query<Entity, Added<MapFrame>>) {
+    let bounds = galaxy.bounds;
+    let span = |min: Option<f32>, max: Option<f32>, low: f32, high: f32| {
+        (min.unwrap_or(low).max(low), max.unwrap_or(high).min(high))
+    };
+
+    for map in &frames {
+        for biome in &galaxy.biomes {
+            let Some(hazard) = biome.hazard else {
+                continue;
+            };
+            let (min_x, max_x) = span(biome.x.min, biome.x.max, bounds.min.x, bounds.max.x);
+            let (min_y, max_y) = span(biome.y.min, biome.y.max, bounds.min.y, bounds.max.y);
+            commands.spawn((
+                Name::new("Hazard Zone"),
+                MapZone(Rect::new(min_x, min_y, max_x, max_y)),
+                Node {
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                BackgroundColor(hazards::tint(hazard).with_alpha(0.4)),
+                ChildOf(map),
+            ));
+        }
+
+        commands.spawn((
+            Name::new("Sector Edge"),
+            MapZone(bounds),
+            Node {
+                position_type: PositionType::Absolute,
+                border: UiRect::all(Val::Px(1.0)),
+                ..default()
+            },
+            BorderColor(Color::srgba(1.0, 0.3, 0.2, 0.8)),
+            ChildOf(map),
+        ));
+    }
+}
+
+fn update_zones(
+    zoom: Res<MapZoom>,
+    galaxy: Res<Galaxy>,
+    player: Single<&Transform, With<Player>>,
+    frames: Query<&MapFrame>,
+    mut zones: Query<(&MapZone, &ChildOf, &mut Node)>,
+) {
+    for (MapZone(rect), child_of, mut node) in &mut zones {
+        let Ok(frame) = frames.get(child_of.parent()) else {
+            continue;
+        };
+        let area = map_area(*frame, &zoom, galaxy.bounds, player.translation.xy());
+        let min = map_percent(area, Vec2::new(rect.min.x, rect.max.y));
+        let max = map_percent(area, Vec2::new(rect.max.x, rect.min.y));
+        node.left = Val::Percent(min.x);
+        node.top = Val::Percent(min.y);
+        node.width = Val::Percent(max.x - min.x);
+        node.height = Val::Percent(max.y - min.y);
+    }
+}
+
+/// Keeps one marker per map for everything that should be on it, and moves them to
+/// where their entities are.
+fn update_markers(
+    mut commands: Commands,
+    zoom: Res<MapZoom>,
+    galaxy: Res<Galaxy>,
+    player: Single<&Transform, With<Player>>,
+    frames: Query<(Entity, &MapFrame)>,
+    tracked: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&Planet>,
+            Has<Player>,
+            Has<FlagshipAI>,
+            Has<OutpostAI>,
+        ),
+        Or<(With<Player>, With<Planet>, With<Enemy>)>,
+    >,
+    mut markers: Query<(
+        Entity,
+        &MapMarker,
+        &ChildOf,
+        &mut Node,
+        &mut BackgroundColor,
+    )>,
+) {
+    let player_pos = player.translation.xy();
+    let blips: HashMap<Entity, (Vec2, Blip)> = tracked
+        .iter()
+        .filter_map(|(ent, trans, planet, is_player, is_flagship, is_outpost)| {
+            let position = trans.translation.xy();
+            let blip = match planet {
+                Some(planet) if planet.has_shopped => Blip::Planet,
+                Some(_) => Blip::Shop,
+                None if is_player => Blip::Player,
+                None if is_flagship => Blip::Flagship,
+                None if is_outpost => Blip::Outpost,
+                None if position.distance(player_pos) < BLIP_RANGE => Blip::Enemy,
+                None => return None,
+            };
+            Some((ent, (position, blip)))
+        })
+        .collect();
+
+    let mut shown = HashMap::new();
+    for (marker, MapMarker(target), child_of, mut node, mut color) in &mut markers {
+        let Ok((_, frame)) = frames.get(child_of.parent()) else {
+            continue;
+        };
+        let Some(&(position, blip)) = blips.get(target) else {
+            commands.entity(marker).despawn();
+            continue;
+        };
+        let area = map_area(*frame, &zoom, galaxy.bounds, player_pos);
+        let percent = map_percent(area, position);
+        node.left = Val::Percent(percent.x);
+        node.top = Val::Percent(percent.y);
+        // Shops turn grey once they've been visited.
+        color.0 = blip.color();
+        shown
+            .entry(child_of.parent())
+            .or_insert_with(Vec::new)
+            .push(*target);
+    }
+
+    for (map, frame) in &frames {
+        let area = map_area(*frame, &zoom, galaxy.bounds, player_pos);
+        let on_map = shown.get(&map);
+        for (&target, &(position, blip)) in &blips {
+            if on_map.is_some_and(|on_map| on_map.contains(&target)) {
+                continue;
+            }
+            commands.spawn(marker(map, target, map_percent(area, position), blip));
+        }
+    }
+}
+
+fn marker(map: Entity, target: Entity, percent: Vec2, blip: Blip) -> impl Bundle {
+    let size = blip.size();
+    (
+        Name::new("Map Marker"),
+        MapMarker(target),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Percent(percent.x),
+            top: Val::Percent(percent.y),
+            width: Val::Px(size),
+            height: Val::Px(size),
+            // centered on the spot
+            margin: UiRect::new(
+                Val::Px(-size / 2.0),
+                Val::ZERO,
+                Val::Px(-size / 2.0),
+                Val::ZERO,
+            ),
+            ..default()
+        },
+        BackgroundColor(blip.color()),
+        blip.z_index(),
+        ChildOf(map),
+    )
+}
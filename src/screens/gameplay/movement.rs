@@ -7,14 +7,13 @@
 //!   This is done in the `player` module, as it is specific to the player
 //!   character.
 //! - Apply movement based on [`MovementController`] intent and maximum speed.
-//! - Wrap the character within the window.
 //!
 //! Note that the implementation used here is limited for demonstration
 //! purposes. If you want to move the player in a smoother way,
 //! consider using a [fixed timestep](https://github.com/bevyengine/bevy/blob/main/examples/movement/physics_in_fixed_timestep.rs).
 
 use avian2d::prelude::*;
-use bevy::{prelude::*, window::PrimaryWindow};
+use bevy::prelude::*;
 
 use crate::{
     screens::{gameplay::upgrade_menu::UpgradeTypes, Screen},
@@ -22,25 +21,24 @@ use crate::{
 };
 
 use super::{
-    level::{BackgroundAccess, Planet},
     player::Player,
     upgrade_menu::{UIShop, Upgrades},
+    GameState,
 };
 
 //use super::player::Player;
 
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<MovementController>();
-    app.register_type::<ScreenWrap>();
 
     app.add_systems(Update, update_camera.run_if(in_state(Screen::Gameplay)));
 
     app.add_systems(
         FixedUpdate,
-        (apply_movement, apply_screen_wrap)
-            .chain()
+        apply_movement
             .in_set(AppSystems::Update)
-            .in_set(PausableSystems),
+            .in_set(PausableSystems)
+            .run_if(in_state(GameState::OpenWorld)),
     );
 }
 
@@ -98,29 +96,10 @@ fn apply_movement(
     }
 }
 
-#[derive(Component, Reflect)]
-#[reflect(Component)]
-pub struct ScreenWrap;
-
-fn apply_screen_wrap(
-    window: Single<&Window, With<PrimaryWindow>>,
-    mut wrap_query: Query<&mut Transform, With<ScreenWrap>>,
-) {
-    let size = window.size() + 256.0;
-    let half_size = size / 2.0;
-    for mut transform in &mut wrap_query {
-        let position = transform.translation.xy();
-        let wrapped = (position + half_size).rem_euclid(size) - half_size;
-        transform.translation = wrapped.extend(transform.translation.z);
-    }
-}
-
 fn update_camera(
     mut set: ParamSet<(
         Query<&mut Transform, With<Camera2d>>,
         Query<&Transform, With<Player>>,
-        Query<&mut Transform, With<BackgroundAccess>>,
-        Query<(&mut Transform, &Planet)>,
     )>,
 ) {
     let p = {
@@ -130,23 +109,7 @@ fn update_camera(
             return;
         }
     };
-    let camera_layer = {
-        let mut camera = set.p0().single_inner().unwrap();
-        camera.translation.x = p.x;
-        camera.translation.y = p.y;
-        camera.translation.z
-    };
-
-    let camera_pos = Vec3::new(p.x, p.y, camera_layer);
-    {
-        let mut p2 = set.p2();
-        let mut background = p2.single_mut().unwrap();
-        background.translation = camera_pos * 0.95 - Vec3::new(0.0, 0.0, 5.0);
-    }
-
-    //Planet paralaxing
-    for (mut transform, init_position) in set.p3().iter_mut() {
-        transform.translation =
-            Vec3::new(init_position.x, init_position.y, -0.5) + camera_pos * 0.9;
-    }
+    let mut camera = set.p0().single_inner().unwrap();
+    camera.translation.x = p.x;
+    camera.translation.y = p.y;
 }
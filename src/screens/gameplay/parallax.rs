@@ -0,0 +1,121 @@
+//! The layered background. Every layer in the sector's `background` is streamed in as
+//! chunks around the camera, one tile of its image each, and anything with a
+//! [`ParallaxDepth`] is moved along with the camera to look further away than it is.
+
+use std::collections::HashSet;
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::screens::Screen;
+
+use super::sector::LayerDefinition;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        PostUpdate,
+        (stream_chunks, apply_parallax_depth)
+            .chain()
+            .before(TransformSystem::TransformPropagate)
+            .run_if(in_state(Screen::Gameplay)),
+    );
+}
+
+/// Drawn at `anchor`, shifted along with the camera by `depth`. 0 sits still in the
+/// world, 1 stays put on screen.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ParallaxDepth {
+    pub depth: f32,
+    pub anchor: Vec2,
+}
+
+/// A background layer. Its chunks are spawned as children.
+#[derive(Component)]
+pub struct ParallaxLayer(pub LayerDefinition);
+
+/// Which tile of its layer a chunk is.
+#[derive(Component)]
+struct ParallaxChunk(IVec2);
+
+pub fn parallax_layer(layer: &LayerDefinition) -> impl Bundle {
+    (
+        Name::new("Parallax Layer"),
+        ParallaxLayer(layer.clone()),
+        Transform::from_xyz(0.0, 0.0, layer.z),
+        Visibility::default(),
+    )
+}
+
+/// Spawns the chunks that came into view and despawns the ones that went out of it.
+fn stream_chunks(
+    mut commands: Commands,
+    window: Single<&Window, With<PrimaryWindow>>,
+    camera: Query<&Transform, With<Camera2d>>,
+    layers: Query<(Entity, &ParallaxLayer, Option<&Children>)>,
+    chunks: Query<&ParallaxChunk>,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera_pos = camera.translation.xy();
+    let half_view = window.size() / 2.0;
+
+    for (ent, ParallaxLayer(layer), children) in &layers {
+        // Where the camera is as far as this layer's chunks are concerned.
+        let center = camera_pos * (1.0 - layer.depth);
+        // One extra chunk around the edges so nothing pops in on screen.
+        let min = ((center - half_view) / layer.tile_size).round().as_ivec2() - 1;
+        let max = ((center + half_view) / layer.tile_size).round().as_ivec2() + 1;
+
+        let mut present = HashSet::new();
+        for &child in children.into_iter().flatten() {
+            let Ok(ParallaxChunk(cell)) = chunks.get(child) else {
+                continue;
+            };
+            if cell.cmplt(min).any() || cell.cmpgt(max).any() {
+                commands.entity(child).despawn();
+            } else {
+                present.insert(*cell);
+            }
+        }
+
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                let cell = IVec2::new(x, y);
+                if present.contains(&cell) {
+                    continue;
+                }
+                commands.spawn((
+                    Name::new("Parallax Chunk"),
+                    ParallaxChunk(cell),
+                    ParallaxDepth {
+                        depth: layer.depth,
+                        anchor: cell.as_vec2() * layer.tile_size,
+                    },
+                    Sprite {
+                        image: layer.image.clone(),
+                        custom_size: Some(layer.tile_size),
+                        color: layer.color,
+                        ..default()
+                    },
+                    ChildOf(ent),
+                ));
+            }
+        }
+    }
+}
+
+fn apply_parallax_depth(
+    camera: Query<&Transform, (With<Camera2d>, Without<ParallaxDepth>)>,
+    mut query: Query<(&ParallaxDepth, &mut Transform)>,
+) {
+    let Ok(camera) = camera.single() else {
+        return;
+    };
+    let camera_pos = camera.translation.xy();
+
+    for (parallax, mut trans) in &mut query {
+        let position = parallax.anchor + camera_pos * parallax.depth;
+        trans.translation.x = position.x;
+        trans.translation.y = position.y;
+    }
+}
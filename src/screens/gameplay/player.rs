@@ -33,11 +33,14 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
+/// How quickly the player slows down without thrust, nebulae add to it.
+pub const LINEAR_DAMPING: f32 = 1.2;
+
 #[derive(Component)]
 pub struct ThrusterUpgrade;
 
 /// The player character.
-pub fn gen_player(max_speed: f32, player_assets: &PlayerAssets) -> impl Bundle {
+pub fn gen_player(max_speed: f32, position: Vec2, player_assets: &PlayerAssets) -> impl Bundle {
     // A texture atlas is a way to split a single image into a grid of related images.
     // You can learn more in this example: https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs
 
@@ -48,7 +51,7 @@ pub fn gen_player(max_speed: f32, player_assets: &PlayerAssets) -> impl Bundle {
             image: player_assets.ducky.clone(),
             ..default()
         },
-        Transform::from_scale(Vec2::splat(2.0).extend(1.0)),
+        Transform::from_translation(position.extend(0.0)).with_scale(Vec2::splat(2.0).extend(1.0)),
         MovementController {
             max_speed,
             ..default()
@@ -73,7 +76,7 @@ fn player_physics_params() -> impl Bundle {
         ExternalTorque::default().with_persistence(false),
         ExternalImpulse::default(),
         AngularDamping(5.0),
-        LinearDamping(1.2),
+        LinearDamping(LINEAR_DAMPING),
         MaxLinearSpeed(1000.0),
         MaxAngularSpeed(PI),
         CollisionEventsEnabled,
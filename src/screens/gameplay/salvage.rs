@@ -0,0 +1,80 @@
+//! Destroyed outposts leave salvage behind. Flying into it gets the player an upgrade,
+//! just like docking at a planet's shop.
+
+use bevy::prelude::*;
+
+use crate::{menus::Menu, screens::Screen};
+
+use super::{
+    GameplayLogic,
+    combat::Health,
+    enemies::{EntityAssets, OutpostAI},
+    level::{PlanetType, UIAssets, VisistedPlanet},
+    player::Player,
+};
+
+/// How close the player has to fly to pick salvage up.
+const PICKUP_RANGE: f32 = 96.0;
+const SALVAGE_COLOR: Color = Color::srgb(1.0, 0.8, 0.3);
+/// The shop salvage opens, no matter where the player docked last. Outposts are
+/// stations too, and this way it's never the final shop.
+const SALVAGE_SHOP: PlanetType = PlanetType::SpaceStation;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(drop_salvage);
+    app.add_systems(Update, collect_salvage.in_set(GameplayLogic));
+}
+
+#[derive(Component)]
+struct Salvage;
+
+/// Outposts also get removed when the level is, only the destroyed ones drop anything.
+fn drop_salvage(
+    trigger: Trigger<OnRemove, OutpostAI>,
+    mut commands: Commands,
+    outposts: Query<(&Transform, &Health)>,
+    entity_assets: Res<EntityAssets>,
+    ui_assets: Res<UIAssets>,
+) {
+    let Ok((trans, health)) = outposts.get(trigger.target()) else {
+        return;
+    };
+    if health.0 >= 0 {
+        return;
+    }
+
+    let position = trans.translation.with_z(0.5);
+    commands.spawn((
+        Transform::from_translation(position).with_scale(Vec3::splat(2.0)),
+        entity_assets.get_explosion(),
+    ));
+    commands.spawn((
+        Name::new("Salvage"),
+        Salvage,
+        StateScoped(Screen::Gameplay),
+        Transform::from_translation(position),
+        Sprite {
+            image: ui_assets.exclamation.clone(),
+            custom_size: Some(Vec2::splat(96.0)),
+            color: SALVAGE_COLOR,
+            ..default()
+        },
+    ));
+}
+
+fn collect_salvage(
+    mut commands: Commands,
+    player: Single<&Transform, With<Player>>,
+    salvage: Query<(Entity, &Transform), With<Salvage>>,
+    mut next_planet: Single<&mut VisistedPlanet>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    for (ent, trans) in &salvage {
+        if trans.translation.xy().distance(player.translation.xy()) < PICKUP_RANGE {
+            commands.entity(ent).despawn();
+            next_planet.0 = SALVAGE_SHOP;
+            next_menu.set(Menu::Buy);
+            return;
+        }
+    }
+}
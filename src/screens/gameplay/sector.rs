@@ -0,0 +1,437 @@
+//! The open world is described in `assets/sectors/*.sector.ron` files: where the
+//! planets are, which enemies show up where, the background and music, and scripted
+//! events. Sectors with a [`Generator`] get a different chain of planets every run, see
+//! `galaxy.rs`.
+//!
+//! Positions are in world units, where the player finds things. x grows towards the
+//! far end of the sector, y is centered on the flagship's path.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use rand::Rng;
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{enemies::ShipType, level::PlanetType};
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<SectorDefinition>()
+        .init_asset_loader::<SectorDefinitionLoader>();
+}
+
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct SectorDefinition {
+    pub name: String,
+    /// Drawn back to front.
+    pub background: Vec<LayerDefinition>,
+    pub music: Handle<AudioSource>,
+    /// x runs from 0 to `size.x`, y from `-size.y` to `size.y`.
+    pub size: Vec2,
+    pub player_start: Vec2,
+    pub flagship_start: Vec2,
+    pub planets: Vec<PlanetDefinition>,
+    /// Outposts placed by hand, on top of the ones the generator adds.
+    pub outposts: Vec<Vec2>,
+    pub biomes: Vec<Biome>,
+    pub generator: Option<Generator>,
+    pub events: Vec<ScriptedEvent>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PlanetDefinition {
+    pub planet: PlanetType,
+    pub position: Vec2,
+    /// Planets without a shop are just scenery.
+    pub shop: bool,
+}
+
+/// One layer of the parallax background, see `parallax.rs`.
+#[derive(Clone, Debug)]
+pub struct LayerDefinition {
+    pub image: Handle<Image>,
+    /// How much the layer moves along with the camera. 0 sits still in the world like
+    /// everything else, 1 is infinitely far away.
+    pub depth: f32,
+    /// The size of one tile of the image, in world units.
+    pub tile_size: Vec2,
+    pub z: f32,
+    pub color: Color,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct Biome {
+    pub name: String,
+    #[serde(default)]
+    pub x: Span,
+    #[serde(default)]
+    pub y: Span,
+    pub enemies: Vec<EnemyMix>,
+    pub pattern: SpawnPattern,
+    /// How much threat the spawn director gets to spend here every second, see
+    /// `director.rs`.
+    pub threat_rate: f32,
+    /// The director stops spawning while this many of its enemies are still around.
+    pub max_alive: usize,
+    #[serde(default)]
+    pub hazard: Option<Hazard>,
+}
+
+impl Biome {
+    pub fn contains(&self, position: Vec2) -> bool {
+        self.x.contains(position.x) && self.y.contains(position.y)
+    }
+
+    /// Picks which enemy to spawn, by weight.
+    pub fn pick_enemy(&self, rng: &mut impl Rng) -> Option<EnemyMix> {
+        let total: u32 = self.enemies.iter().map(|enemy| enemy.weight).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.random_range(0..total);
+        self.enemies.iter().copied().find(|enemy| {
+            if roll < enemy.weight {
+                true
+            } else {
+                roll -= enemy.weight;
+                false
+            }
+        })
+    }
+}
+
+/// How to lay out a sector's planets from a seed. They're strung along the x axis from
+/// the home planet to the goal, one stop every `min_spacing` to `max_spacing`, with some
+/// stops forking into a planet above and one below the path.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Generator {
+    /// Where the run starts.
+    pub home: PlanetType,
+    pub start: f32,
+    /// The last planet, where the research lab is.
+    pub goal: PlanetType,
+    pub goal_x: f32,
+    pub min_spacing: f32,
+    pub max_spacing: f32,
+    /// The chance of a stop forking.
+    pub fork_chance: f64,
+    /// How far above and below the path forked planets are.
+    pub fork_offset: f32,
+    /// How far off the path the other planets can be.
+    pub jitter: f32,
+    /// The planets in between, all of them get used once before any repeat.
+    pub planets: Vec<PlanetType>,
+    /// The chance of an outpost guarding a stop. The home planet and the goal never get
+    /// one. Outposts are spawned and run in `enemies.rs` (`gen_outpost` and
+    /// `process_outpost_ai`), their turrets are `OutpostTurret` in `combat/weapons.rs` and
+    /// what they drop is in `salvage.rs`.
+    #[serde(default)]
+    pub outpost_chance: f64,
+    /// How far from the planet it guards an outpost is.
+    #[serde(default = "default_outpost_distance")]
+    pub outpost_distance: f32,
+    /// Biomes for the stretches between stops, their `x` is decided by the generator.
+    /// The first one is always where the run starts.
+    pub regions: Vec<Biome>,
+}
+
+/// Something in a biome that makes flying through it harder, see `hazards.rs`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Hazard {
+    /// Thick gas that slows the player down and blinds the minimap.
+    Nebula { damping: f32 },
+    /// Knocks out lasers and electric fields.
+    IonStorm,
+    /// Every `interval` seconds everything near the player takes `damage`, with a
+    /// countdown for the last `warning` seconds.
+    SolarFlare {
+        interval: f32,
+        warning: f32,
+        damage: i32,
+    },
+    /// Keeps `count` slowly drifting asteroids around the player.
+    Debris { count: usize },
+}
+
+/// A range along one axis, unbounded where a side is left out.
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub struct Span {
+    #[serde(default)]
+    pub min: Option<f32>,
+    #[serde(default)]
+    pub max: Option<f32>,
+}
+
+impl Span {
+    pub fn contains(&self, value: f32) -> bool {
+        self.min.is_none_or(|min| min <= value) && self.max.is_none_or(|max| value < max)
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct EnemyMix {
+    /// How the enemy behaves.
+    pub ai: ShipType,
+    /// What the enemy looks like, only used by rammers.
+    pub look: ShipType,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    /// How much of the director's threat budget one of these takes.
+    #[serde(default = "default_cost")]
+    pub cost: f32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_cost() -> f32 {
+    1.0
+}
+
+/// Where around the player enemies appear.
+#[derive(Deserialize, Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum SpawnPattern {
+    Circle,
+    Top,
+    Bot,
+    Right,
+    /// Evenly spaced all around the player when spawned as a group, like `Circle`
+    /// otherwise.
+    Ring,
+}
+
+impl SpawnPattern {
+    /// Which direction from the player to spawn in, clockwise from straight up.
+    pub fn angle(self, rng: &mut impl Rng) -> f32 {
+        let degrees: f32 = match self {
+            SpawnPattern::Circle | SpawnPattern::Ring => rng.random_range(0.0..360.0),
+            SpawnPattern::Top => rng.random_range(-45.0..45.0),
+            SpawnPattern::Bot => rng.random_range(135.0..225.0),
+            SpawnPattern::Right => rng.random_range(45.0..135.0),
+        };
+        degrees.to_radians()
+    }
+}
+
+/// A set piece, see `timeline.rs`. Once everything in `when` holds, everything in
+/// `then` happens, once per run.
+#[derive(Clone, Debug)]
+pub struct ScriptedEvent {
+    pub name: String,
+    pub when: Vec<Condition>,
+    pub then: Vec<EventAction>,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum Condition {
+    /// The player made it past this x.
+    PlayerX(f32),
+    /// Seconds spent in the open world this run.
+    Elapsed(f32),
+    /// The player has been to the shop on a planet like this. Shopping pauses the
+    /// game, so this goes off as they leave.
+    Visited(PlanetType),
+    /// The flagship is at most this far behind the player.
+    FlagshipWithin(f32),
+}
+
+#[derive(Clone, Debug)]
+pub enum EventAction {
+    /// `count` enemies at once, coming in from `pattern`.
+    Spawn {
+        enemy: EnemyMix,
+        count: usize,
+        pattern: SpawnPattern,
+    },
+    /// Shown at the top of the screen for a few seconds.
+    Message(String),
+    /// Replaces the sector's music for the rest of the run.
+    Music(Handle<AudioSource>),
+}
+
+#[derive(Deserialize)]
+struct SectorFile {
+    name: String,
+    background: Vec<LayerFile>,
+    music: String,
+    size: (f32, f32),
+    player_start: (f32, f32),
+    flagship_start: (f32, f32),
+    #[serde(default)]
+    planets: Vec<PlanetFile>,
+    #[serde(default)]
+    outposts: Vec<(f32, f32)>,
+    #[serde(default)]
+    biomes: Vec<Biome>,
+    #[serde(default)]
+    generator: Option<Generator>,
+    #[serde(default)]
+    events: Vec<EventFile>,
+}
+
+#[derive(Deserialize)]
+struct EventFile {
+    name: String,
+    when: Vec<Condition>,
+    then: Vec<ActionFile>,
+}
+
+#[derive(Deserialize)]
+enum ActionFile {
+    Spawn {
+        enemy: EnemyMix,
+        count: usize,
+        pattern: SpawnPattern,
+    },
+    Message(String),
+    Music(String),
+}
+
+#[derive(Deserialize)]
+struct PlanetFile {
+    planet: PlanetType,
+    position: (f32, f32),
+    #[serde(default = "default_shop")]
+    shop: bool,
+}
+
+fn default_shop() -> bool {
+    true
+}
+
+fn default_outpost_distance() -> f32 {
+    1500.0
+}
+
+#[derive(Deserialize)]
+struct LayerFile {
+    image: String,
+    depth: f32,
+    tile_size: (f32, f32),
+    z: f32,
+    #[serde(default = "default_color")]
+    color: (f32, f32, f32, f32),
+}
+
+fn default_color() -> (f32, f32, f32, f32) {
+    (1.0, 1.0, 1.0, 1.0)
+}
+
+#[derive(Default)]
+struct SectorDefinitionLoader;
+
+#[derive(Debug, Error)]
+enum SectorDefinitionLoaderError {
+    #[error("could not read sector file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse sector file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("generator spacing `{0}..={1}` has to be above 0, with the minimum first")]
+    Spacing(f32, f32),
+    #[error("generator `jitter` can't be negative, got {0}")]
+    Jitter(f32),
+}
+
+impl AssetLoader for SectorDefinitionLoader {
+    type Asset = SectorDefinition;
+    type Settings = ();
+    type Error = SectorDefinitionLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        use crate::util::make_nearest;
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: SectorFile = ron::de::from_bytes(&bytes)?;
+        if let Some(generator) = &file.generator {
+            let (min, max) = (generator.min_spacing, generator.max_spacing);
+            if min <= 0.0 || min > max {
+                return Err(SectorDefinitionLoaderError::Spacing(min, max));
+            }
+            if generator.jitter < 0.0 {
+                return Err(SectorDefinitionLoaderError::Jitter(generator.jitter));
+            }
+        }
+
+        let background = file
+            .background
+            .iter()
+            .map(|layer| {
+                let (r, g, b, a) = layer.color;
+                LayerDefinition {
+                    image: load_context
+                        .loader()
+                        .with_settings(make_nearest)
+                        .load(&layer.image),
+                    depth: layer.depth,
+                    tile_size: layer.tile_size.into(),
+                    z: layer.z,
+                    color: Color::srgba(r, g, b, a),
+                }
+            })
+            .collect();
+        let music = load_context.load(&file.music);
+
+        Ok(SectorDefinition {
+            name: file.name,
+            background,
+            music,
+            size: file.size.into(),
+            player_start: file.player_start.into(),
+            flagship_start: file.flagship_start.into(),
+            planets: file
+                .planets
+                .iter()
+                .map(|planet| PlanetDefinition {
+                    planet: planet.planet,
+                    position: planet.position.into(),
+                    shop: planet.shop,
+                })
+                .collect(),
+            outposts: file
+                .outposts
+                .iter()
+                .map(|&outpost| outpost.into())
+                .collect(),
+            biomes: file.biomes,
+            generator: file.generator,
+            events: file
+                .events
+                .into_iter()
+                .map(|event| ScriptedEvent {
+                    name: event.name,
+                    when: event.when,
+                    then: event
+                        .then
+                        .into_iter()
+                        .map(|action| match action {
+                            ActionFile::Spawn {
+                                enemy,
+                                count,
+                                pattern,
+                            } => EventAction::Spawn {
+                                enemy,
+                                count,
+                                pattern,
+                            },
+                            ActionFile::Message(text) => EventAction::Message(text),
+                            ActionFile::Music(path) => EventAction::Music(load_context.load(path)),
+                        })
+                        .collect(),
+                })
+                .collect(),
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["sector.ron"]
+    }
+}
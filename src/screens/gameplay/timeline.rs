@@ -0,0 +1,182 @@
+//! Plays the sector's scripted events, the set pieces on top of what the spawn director
+//! sends. An event goes off the first frame all of its conditions hold and never again
+//! that run. See [`ScriptedEvent`] for what they can do.
+
+use std::{collections::HashSet, f32::consts::TAU};
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::screens::Screen;
+
+use super::{
+    GameplayLogic,
+    enemies::{EntityAssets, FlagshipAI},
+    level::{LevelAssets, MainOST, Planet, PlanetType, UIAssets, spawn_enemy},
+    player::Player,
+    sector::{Condition, EventAction, ScriptedEvent, SectorDefinition, SpawnPattern},
+};
+
+/// How long messages stay up.
+const MESSAGE_SECS: f32 = 4.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Timeline>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_timeline);
+    app.add_systems(
+        Update,
+        (run_timeline, expire_messages).in_set(GameplayLogic),
+    );
+}
+
+#[derive(Resource, Default)]
+struct Timeline {
+    /// Seconds spent in the open world this run.
+    elapsed: f32,
+    /// Indices into the sector's events.
+    fired: HashSet<usize>,
+}
+
+#[derive(Component)]
+struct TimelineMessage(Timer);
+
+fn reset_timeline(mut timeline: ResMut<Timeline>) {
+    *timeline = Timeline::default();
+}
+
+/// Everything the conditions get checked against.
+struct Situation {
+    elapsed: f32,
+    player: Vec2,
+    flagship: Vec2,
+    visited: Vec<PlanetType>,
+}
+
+impl Situation {
+    fn holds(&self, condition: &Condition) -> bool {
+        match *condition {
+            Condition::PlayerX(x) => self.player.x >= x,
+            Condition::Elapsed(secs) => self.elapsed >= secs,
+            Condition::Visited(planet) => self.visited.contains(&planet),
+            Condition::FlagshipWithin(distance) => self.player.distance(self.flagship) <= distance,
+        }
+    }
+
+    fn triggers(&self, event: &ScriptedEvent) -> bool {
+        event.when.iter().all(|condition| self.holds(condition))
+    }
+}
+
+fn run_timeline(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut timeline: ResMut<Timeline>,
+    level_assets: Res<LevelAssets>,
+    sectors: Res<Assets<SectorDefinition>>,
+    entity_assets: Res<EntityAssets>,
+    ui_assets: Res<UIAssets>,
+    player: Single<&Transform, With<Player>>,
+    flagship: Single<&Transform, With<FlagshipAI>>,
+    planets: Query<&Planet>,
+    music: Query<Entity, With<MainOST>>,
+) {
+    timeline.elapsed += time.delta_secs();
+    let Some(sector) = sectors.get(&level_assets.sector) else {
+        return;
+    };
+
+    let situation = Situation {
+        elapsed: timeline.elapsed,
+        player: player.translation.xy(),
+        flagship: flagship.translation.xy(),
+        visited: planets
+            .iter()
+            .filter(|planet| planet.visited)
+            .map(|planet| planet.planet_type)
+            .collect(),
+    };
+
+    for (i, event) in sector.events.iter().enumerate() {
+        if timeline.fired.contains(&i) || !situation.triggers(event) {
+            continue;
+        }
+        timeline.fired.insert(i);
+        log::info!("scripted event `{}`", event.name);
+
+        for action in &event.then {
+            match action {
+                EventAction::Spawn {
+                    enemy,
+                    count,
+                    pattern,
+                } => {
+                    let mut rng = rand::rng();
+                    let offset = rng.random_range(0.0..TAU);
+                    for n in 0..*count {
+                        let angle = match pattern {
+                            SpawnPattern::Ring => offset + TAU * n as f32 / *count as f32,
+                            _ => pattern.angle(&mut rng),
+                        };
+                        spawn_enemy(
+                            &mut commands,
+                            &entity_assets,
+                            *enemy,
+                            angle,
+                            player.translation,
+                        );
+                    }
+                }
+                EventAction::Message(text) => {
+                    commands.spawn(message(text, &ui_assets));
+                }
+                EventAction::Music(handle) => {
+                    for ost in &music {
+                        commands.entity(ost).despawn();
+                    }
+                    commands.spawn((
+                        MainOST,
+                        AudioPlayer::new(handle.clone()),
+                        PlaybackSettings::LOOP,
+                        StateScoped(Screen::Gameplay),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+fn message(text: &str, ui_assets: &UIAssets) -> impl Bundle {
+    (
+        Name::new("Timeline Message"),
+        TimelineMessage(Timer::from_seconds(MESSAGE_SECS, TimerMode::Once)),
+        StateScoped(Screen::Gameplay),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            top: Val::Percent(20.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        children![(
+            Text::new(text),
+            TextFont {
+                font: ui_assets.font.clone(),
+                font_size: 40.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.9, 0.8, 0.4)),
+        )],
+    )
+}
+
+fn expire_messages(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut messages: Query<(Entity, &mut TimelineMessage)>,
+) {
+    for (ent, mut message) in &mut messages {
+        if message.0.tick(time.delta()).finished() {
+            commands.entity(ent).despawn();
+        }
+    }
+}
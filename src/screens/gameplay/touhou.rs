@@ -1,17 +1,60 @@
+//! The bullet-hell phase of the game.
+//!
+//! Once the player has bought the [`UpgradeTypes::Emp`] and flies close to the
+//! flagship, the open world is frozen and the fight continues as a touhou-style
+//! encounter in [`GameState::Touhou`]. Winning ends the run on the victory
+//! screen, losing throws the player back into the open world.
+
+use std::{iter, time::Duration};
+
+use avian2d::prelude::{Physics, PhysicsTime};
 use bevy::{
-    ecs::query::QueryFilter, input::common_conditions::input_just_pressed,
-    render::camera::ScalingMode,
+    ecs::query::QueryFilter,
+    input::common_conditions::input_just_pressed,
+    prelude::*,
+    render::{camera::ScalingMode, view::RenderLayers},
+};
+use boss::BossDefinition;
+use bullet::{AltFire, Tech};
+use enemy::{EnemyMarker, Health};
+use input::{InputFrame, TouhouInput};
+
+pub use difficulty::Difficulty;
+
+use crate::{Pause, asset_tracking::LoadResource, screens::Screen};
+
+use super::{
+    GameState, GameplayLogic,
+    combat::Damage,
+    enemies::FlagshipAI,
+    level::{UIAssets, UIBox},
+    player::Player as OpenWorldPlayer,
+    upgrade_menu::{UpgradeTypes, Upgrades},
 };
-use bullet::AltFire;
-use enemy::{Animation, EnemyMarker, Health};
-
-use crate::prelude::*;
 
+#[cfg(feature = "dev")]
+mod benchmark;
+mod boss;
+mod boss_hud;
 mod bullet;
+mod difficulty;
 mod enemy;
+mod input;
+mod laser;
+mod practice;
+mod replay;
+mod spatial_hash;
+
+/// Everything belonging to the encounter is drawn on its own layer, so the
+/// frozen open world around the origin doesn't bleed into the arena.
+const TOUHOU_LAYER: usize = 1;
+
+/// How close the player has to fly to the flagship to start the encounter.
+const ENGAGE_DISTANCE: f32 = 700.0;
 
 #[derive(Component, Clone, Default, Debug)]
-struct TouhouMarker;
+#[require(RenderLayers = RenderLayers::layer(TOUHOU_LAYER))]
+pub struct TouhouMarker;
 #[derive(Component, Default, Debug)]
 struct PlayerMarker;
 #[derive(Component, Default)]
@@ -25,19 +68,15 @@ struct PlayerFilter {
 type PlayerQ<'a, T> = Single<'a, T, With<PlayerMarker>>;
 
 #[derive(Component, Default, Copy, Clone, Debug)]
-struct Collider {
+pub struct Collider {
     radius: f32,
 }
 
 impl Collider {
-    fn to_circle(&self, pos: Vec2) -> Circle {
-        let Self { radius } = *self;
+    fn to_circle(self, pos: Vec2) -> Circle {
+        let Self { radius } = self;
         Circle { pos, radius }
     }
-
-    fn new(radius: f32) -> Self {
-        Self { radius }
-    }
 }
 
 #[derive(Default, Copy, Clone, Debug)]
@@ -56,21 +95,32 @@ impl Circle {
         Self { pos, radius }
     }
 
-    #[allow(dead_code)]
-    fn within(&self, rect: Rect) -> bool {
-        let Self { pos, radius } = *self;
-
-        let bounding_rect = Rect::from_center_size(pos, Vec2::splat(radius));
-
-        rect.contains(bounding_rect.min) && rect.contains(bounding_rect.max)
+    fn hits(&self, other: Circle) -> bool {
+        (self.pos - other.pos).length() - (self.radius + other.radius) < 0.0
     }
+}
+
+/// A line segment with thickness, used for beams.
+#[derive(Default, Copy, Clone, Debug)]
+struct Capsule {
+    start: Vec2,
+    end: Vec2,
+    radius: f32,
+}
 
+impl Capsule {
     fn hits(&self, other: Circle) -> bool {
-        (self.pos - other.pos).length() - (self.radius + other.radius) < 0.0
+        let line = self.end - self.start;
+        let along = (other.pos - self.start).dot(line) / line.length_squared().max(f32::EPSILON);
+        let closest = self.start + line * along.clamp(0.0, 1.0);
+
+        Circle::new(self.radius, closest).hits(other)
     }
 }
 
-#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+/// How the current encounter is going. Only exists while in [`GameState::Touhou`].
+#[derive(SubStates, Clone, Copy, Default, Eq, PartialEq, Debug, Hash)]
+#[source(GameState = GameState::Touhou)]
 pub enum MissionState {
     #[default]
     Ongoing,
@@ -78,6 +128,53 @@ pub enum MissionState {
     Fail,
 }
 
+/// Parameters for the next encounter, decided before entering [`GameState::Touhou`].
+#[derive(Resource)]
+pub struct MissionParams {
+    /// Every piece of tech the player brings, and whether it's bound to alt-fire.
+    pub loadout: Vec<(Tech, bool)>,
+}
+
+impl Default for MissionParams {
+    fn default() -> Self {
+        Self {
+            loadout: vec![(Tech::MachineGun, false), (Tech::Rocket, true)],
+        }
+    }
+}
+
+/// More guns than this and they'd stick out of the ship.
+const MAX_MACHINE_GUNS: usize = 4;
+
+impl MissionParams {
+    /// Translates what the player bought at planets into bullet-hell tech, so the run's
+    /// build decides how the boss fight plays. Every ship starts out with level 1
+    /// cannons, shield and thrusters, so only levels beyond that add anything.
+    pub fn from_upgrades(upgrades: &Upgrades) -> Self {
+        let level = |upgrade| upgrades.gotten_upgrades.get(&upgrade).copied().unwrap_or(0);
+        let bought = |upgrade| level(upgrade).saturating_sub(1);
+
+        let guns = level(UpgradeTypes::Cannon).clamp(1, MAX_MACHINE_GUNS);
+
+        let mut loadout = vec![(Tech::Rocket, true)];
+        loadout.extend(iter::repeat_n((Tech::MachineGun, false), guns));
+        loadout.extend(iter::repeat_n(
+            (Tech::LaserStream, false),
+            level(UpgradeTypes::Laser),
+        ));
+        loadout.extend(iter::repeat_n(
+            (Tech::Thruster, false),
+            bought(UpgradeTypes::Thrusters),
+        ));
+        loadout.extend(iter::repeat_n(
+            (Tech::ExtraLife, false),
+            bought(UpgradeTypes::Health),
+        ));
+
+        Self { loadout }
+    }
+}
+
 #[derive(Resource, Default)]
 struct GameplayRect {
     rect: Rect,
@@ -86,70 +183,99 @@ struct GameplayRect {
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 enum TouhouSets {
     EnterTouhou,
+    /// Fills in [`TouhouInput`] for this tick, from the keyboard or a replay.
+    Input,
     Gameplay,
 }
 
 #[derive(Component, Deref, DerefMut, Default)]
 struct Speed(f32);
 
-#[derive(Bundle)]
-struct AnimatedSprite {
-    sprite: Sprite,
-    animation: Animation,
-}
+/// The player is holding focus: slower, with tighter guns and the hitbox shown.
+#[derive(Component)]
+struct Focused;
 
-pub fn make_animation(
-    sprite: Sprite,
-    layout: Handle<TextureAtlasLayout>,
-    time: f32,
-    n_anims: usize,
-) -> AnimatedSprite {
-    AnimatedSprite {
-        sprite: Sprite {
-            texture_atlas: Some(TextureAtlas { layout, index: 0 }),
-            ..sprite
-        },
-        animation: Animation::new(time, n_anims, 0),
-    }
-}
+/// How much of its speed the player keeps while focused.
+const FOCUS_SPEED: f32 = 0.4;
 
-#[derive(Resource)]
-pub struct PlayerAssets {
-    dead: Handle<Image>,
-    atlas_layout: TextureAtlasLayout,
-    atlas_handle: Handle<TextureAtlasLayout>,
-    alive_sheet: Handle<Image>,
-}
+/// Shows the player's [`Collider`] while focused. Unit sized, scaled to the collider.
+#[derive(Component)]
+struct Hitbox;
+
+/// Counts down before leaving the encounter, so the player gets to see how it ended.
+#[derive(Resource, Deref, DerefMut)]
+struct MissionEndTimer(Timer);
 
-pub fn touhou_plugin(app: &mut App) {
+/// Keeps the player from getting pulled straight back into the encounter after losing it.
+#[derive(Resource, Deref, DerefMut)]
+struct EngageCooldown(Timer);
+
+const MISSION_END_SECS: f32 = 3.0;
+const ENGAGE_COOLDOWN_SECS: f32 = 10.0;
+const FAILED_MISSION_DAMAGE: i32 = 50;
+
+pub(super) fn plugin(app: &mut App) {
     let touhou_gameplay_pred = || {
-        TouhouSets::Gameplay
-            .run_if(in_state(GameState::Touhou).and(in_state(MissionState::Ongoing)))
+        TouhouSets::Gameplay.run_if(
+            in_state(MissionState::Ongoing)
+                .and(in_state(Pause(false)))
+                .and(resource_exists::<TouhouAssets>),
+        )
     };
 
+    app.register_type::<TouhouAssets>();
+    app.load_resource::<TouhouAssets>();
+
+    app.add_plugins(boss::boss_plugin);
+    app.add_plugins(boss_hud::boss_hud_plugin);
+    app.add_plugins(difficulty::difficulty_plugin);
+    app.add_plugins(input::input_plugin);
+    app.add_plugins(laser::laser_plugin);
+    app.add_plugins(replay::replay_plugin);
+    app.add_plugins(practice::practice_plugin);
+    #[cfg(feature = "dev")]
+    app.add_plugins(benchmark::benchmark_plugin);
     app.add_plugins((bullet::bullet_plugin, enemy::enemy_plugin))
-        .init_state::<MissionState>()
+        .add_sub_state::<MissionState>()
         .insert_resource(ShowGizmos { enabled: false })
+        .insert_resource(EngageCooldown(Timer::from_seconds(0.0, TimerMode::Once)))
+        .init_resource::<MissionParams>()
+        .add_systems(Startup, create_gameplay_rect)
         .add_systems(
-            Startup,
-            (load_player_assets, load_touhou_assets, create_gameplay_rect),
+            Update,
+            (tick_engage_cooldown, engage_flagship)
+                .chain()
+                .in_set(GameplayLogic),
         )
         .add_systems(
             OnEnter(GameState::Touhou),
             (
+                freeze_open_world,
                 spawn_player,
                 make_bg,
                 bullet::config_loadout.after(spawn_player),
+                spawn_hitbox.after(spawn_player),
                 make_game_camera,
-                set_mission_status,
-                play_music,
                 spawn_hud,
             )
                 .in_set(TouhouSets::EnterTouhou),
         )
         .add_systems(
             FixedUpdate,
-            (update_invulnerability, do_movement, update_hud).in_set(TouhouSets::Gameplay),
+            (
+                update_invulnerability,
+                do_movement,
+                update_hud,
+                update_score_hud,
+                update_bomb_hud,
+            )
+                .in_set(TouhouSets::Gameplay),
+        )
+        .add_systems(
+            FixedPreUpdate,
+            set_focus
+                .after(TouhouSets::Input)
+                .in_set(TouhouSets::Gameplay),
         )
         .add_systems(
             FixedPostUpdate,
@@ -159,22 +285,138 @@ pub fn touhou_plugin(app: &mut App) {
             FixedPostUpdate,
             (on_death.run_if(player_dead), on_damage)
                 .chain()
-                .after(bullet::process_player_hits),
+                .after(bullet::process_player_hits)
+                .in_set(TouhouSets::Gameplay),
         )
         .add_systems(
             Update,
             (
-                scroll_map.run_if(in_state(GameState::Touhou)),
+                scroll_map,
                 toggle_gizmos.run_if(input_just_pressed(KeyCode::Space)),
                 animate_player,
+                show_hitbox,
+            )
+                .run_if(in_state(GameState::Touhou)),
+        )
+        .add_systems(OnEnter(MissionState::Success), start_mission_end_timer)
+        .add_systems(OnEnter(MissionState::Fail), start_mission_end_timer)
+        .add_systems(
+            Update,
+            leave_touhou.run_if(
+                in_state(GameState::Touhou)
+                    .and(not(in_state(MissionState::Ongoing)))
+                    .and(in_state(Pause(false))),
             ),
         )
         .add_systems(PostUpdate, draw_gizmos.in_set(TouhouSets::Gameplay))
-        // set them all to only run if gamestate is touhou
+        // set them all to only run while the encounter is ongoing
+        .configure_sets(Update, touhou_gameplay_pred())
+        .configure_sets(PostUpdate, touhou_gameplay_pred())
         .configure_sets(FixedUpdate, touhou_gameplay_pred())
         .configure_sets(FixedPreUpdate, touhou_gameplay_pred())
         .configure_sets(FixedPostUpdate, touhou_gameplay_pred())
-        .add_systems(OnExit(GameState::Touhou), nuke_touhou);
+        .configure_sets(
+            FixedPreUpdate,
+            TouhouSets::Input.in_set(TouhouSets::Gameplay),
+        )
+        .add_systems(OnExit(GameState::Touhou), (nuke_touhou, thaw_open_world));
+}
+
+fn tick_engage_cooldown(time: Res<Time>, mut cooldown: ResMut<EngageCooldown>) {
+    cooldown.tick(time.delta());
+}
+
+/// Starts the encounter once the flagship's shield is down and the player is close enough.
+fn engage_flagship(
+    cooldown: Res<EngageCooldown>,
+    player: Single<(&Transform, &Upgrades), With<OpenWorldPlayer>>,
+    flagship: Single<&Transform, With<FlagshipAI>>,
+    mut mission_params: ResMut<MissionParams>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let (player_trans, upgrades) = player.into_inner();
+
+    if !cooldown.finished() || !upgrades.gotten_upgrades.contains_key(&UpgradeTypes::Emp) {
+        return;
+    }
+
+    if player_trans
+        .translation
+        .xy()
+        .distance(flagship.translation.xy())
+        < ENGAGE_DISTANCE
+    {
+        *mission_params = MissionParams::from_upgrades(upgrades);
+        next_state.set(GameState::Touhou);
+    }
+}
+
+fn freeze_open_world(
+    mut time: ResMut<Time<Physics>>,
+    mut cameras: Query<&mut Camera, (With<Camera2d>, Without<TouhouCamera>)>,
+    mut ui: Query<&mut Visibility, With<UIBox>>,
+) {
+    time.pause();
+    for mut camera in &mut cameras {
+        camera.is_active = false;
+    }
+    for mut visibility in &mut ui {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+fn thaw_open_world(
+    mut time: ResMut<Time<Physics>>,
+    mut cameras: Query<&mut Camera, (With<Camera2d>, Without<TouhouCamera>)>,
+    mut ui: Query<&mut Visibility, With<UIBox>>,
+) {
+    time.unpause();
+    for mut camera in &mut cameras {
+        camera.is_active = true;
+    }
+    for mut visibility in &mut ui {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn start_mission_end_timer(mut commands: Commands) {
+    commands.insert_resource(MissionEndTimer(Timer::from_seconds(
+        MISSION_END_SECS,
+        TimerMode::Once,
+    )));
+}
+
+fn leave_touhou(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut timer: ResMut<MissionEndTimer>,
+    mission_state: Res<State<MissionState>>,
+    player: Single<Entity, With<OpenWorldPlayer>>,
+    mut cooldown: ResMut<EngageCooldown>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    practice: Option<Res<practice::Practice>>,
+) {
+    timer.tick(time.delta());
+    if !timer.finished() {
+        return;
+    }
+
+    if practice.is_some() {
+        next_screen.set(Screen::Title);
+        return;
+    }
+
+    match mission_state.get() {
+        MissionState::Success => next_screen.set(Screen::Victory),
+        MissionState::Fail => {
+            commands.trigger_targets(Damage(FAILED_MISSION_DAMAGE), *player);
+            cooldown.set_duration(Duration::from_secs_f32(ENGAGE_COOLDOWN_SECS));
+            cooldown.reset();
+            next_game_state.set(GameState::OpenWorld);
+        }
+        MissionState::Ongoing => {}
+    }
 }
 
 #[derive(Component)]
@@ -182,9 +424,13 @@ struct AmmoCount;
 #[derive(Component)]
 struct LifeCount;
 #[derive(Component)]
-struct HPBar;
+struct ScoreCount;
+#[derive(Component)]
+struct GrazeCount;
+#[derive(Component)]
+struct BombCount;
 
-fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
+fn spawn_hud(mut commands: Commands, ui_assets: Res<UIAssets>) {
     commands
         .spawn((
             TouhouMarker,
@@ -202,7 +448,7 @@ fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
             Text::new("lol"),
             AmmoCount,
             TextFont {
-                font: asset_server.load("fonts/Pixelfont/slkscr.ttf"),
+                font: ui_assets.font.clone(),
                 font_size: 33.0,
                 ..default()
             },
@@ -222,84 +468,126 @@ fn spawn_hud(mut commands: Commands, asset_server: Res<AssetServer>) {
             BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
             ZIndex(1),
         ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("lol"),
+                LifeCount,
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 33.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.9, 0.4, 0.4)),
+            ));
+            parent.spawn((
+                Text::new("lol"),
+                BombCount,
+                Node {
+                    margin: UiRect::left(Val::Px(24.0)),
+                    ..default()
+                },
+                TextFont {
+                    font: ui_assets.font.clone(),
+                    font_size: 33.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.4, 0.9, 0.4)),
+            ));
+        });
+
+    commands
+        .spawn((
+            TouhouMarker,
+            Node {
+                width: Val::Vw(20.0),
+                height: Val::Vh(10.0),
+                left: Val::Vw(80.),
+                bottom: Val::Px(0.),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(1),
+        ))
         .with_child((
             Text::new("lol"),
-            LifeCount,
+            ScoreCount,
             TextFont {
-                font: asset_server.load("fonts/Pixelfont/slkscr.ttf"),
+                font: ui_assets.font.clone(),
                 font_size: 33.0,
                 ..default()
             },
-            TextColor(Color::srgb(0.9, 0.4, 0.4)),
+            TextColor(Color::srgb(0.9, 0.9, 0.5)),
         ));
 
-    commands.spawn((
-        TouhouMarker,
-        Node {
-            width: Val::Vw(80.0),
-            height: Val::Vh(10.0),
-            left: Val::Vw(10.),
-            bottom: -Val::Vh(90.),
-            ..default()
-        },
-        HPBar,
-        BackgroundColor(Color::srgba(1.0, 0.0, 0.0, 0.95)),
-        ZIndex(1),
-    ));
+    commands
+        .spawn((
+            TouhouMarker,
+            Node {
+                width: Val::Vw(20.0),
+                height: Val::Vh(10.0),
+                left: Val::Vw(80.),
+                bottom: -Val::Vh(10.),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(1),
+        ))
+        .with_child((
+            Text::new("lol"),
+            GrazeCount,
+            TextFont {
+                font: ui_assets.font.clone(),
+                font_size: 33.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.5, 0.9, 0.9)),
+        ));
 }
 
 fn update_hud(
     player: Option<Single<(&Ammo, &Life), PlayerFilter>>,
-    enemy_hp: Option<Single<&Health, With<EnemyMarker>>>,
     mut ammo_text: Query<&mut Text, (With<AmmoCount>, Without<LifeCount>)>,
     mut hp_text: Query<&mut Text, (With<LifeCount>, Without<AmmoCount>)>,
-    mut hp_bar: Query<&mut Node, (With<HPBar>)>,
 ) {
     let Some((ammo_count, lives_count)) = player.map(|x| x.into_inner()) else {
         return;
     };
-    let Some(enemy_hp) = enemy_hp else {
-        return;
-    };
     for mut text in &mut ammo_text {
-        **text = format!["Ammo: {}", **ammo_count];
+        **text = format!("Ammo: {}", **ammo_count);
     }
 
     for mut text in &mut hp_text {
-        **text = format!["Lives: {}", **lives_count];
+        **text = format!("Lives: {}", **lives_count);
     }
+}
 
-    for mut node in &mut hp_bar {
-        *node = Node {
-            width: Val::Vw(80. * (***enemy_hp as f32) / 4000.),
-            height: Val::Vh(10.0),
-            left: Val::Vw(10.),
-            bottom: -Val::Vh(90.),
-            ..default()
-        }
+fn update_score_hud(
+    player: Option<Single<(&Score, &Graze), PlayerFilter>>,
+    mut score_text: Query<&mut Text, (With<ScoreCount>, Without<GrazeCount>)>,
+    mut graze_text: Query<&mut Text, (With<GrazeCount>, Without<ScoreCount>)>,
+) {
+    let Some((score, graze)) = player.map(|x| x.into_inner()) else {
+        return;
+    };
+    for mut text in &mut score_text {
+        **text = format!("Score: {}", **score);
+    }
+
+    for mut text in &mut graze_text {
+        **text = format!("Graze: {}", **graze);
     }
 }
 
-fn play_music(
-    mission_params: Res<MissionParams>,
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    assets: Res<TouhouAssets>,
+fn update_bomb_hud(
+    player: Option<Single<&Bombs, PlayerFilter>>,
+    mut bomb_text: Query<&mut Text, With<BombCount>>,
 ) {
-    commands.spawn((
-        PlaybackSettings {
-            mode: bevy::audio::PlaybackMode::Loop,
-            ..default()
-        },
-        AudioPlayer::new(asset_server.load(match mission_params.enemy {
-            Enemies::RedGirl => "Music/Comabt1.ogg",
-            Enemies::Lizard => "Music/Combat2.ogg",
-            Enemies::Tentacle => "Music/Combat3.ogg",
-            Enemies::MoonGirl => "Music/Combat4.ogg",
-            _ => "Music/Comabt1.ogg",
-        })),
-        TouhouMarker,
-    ));
+    let Some(bombs) = player else {
+        return;
+    };
+    for mut text in &mut bomb_text {
+        **text = format!("Bombs: {}", ***bombs);
+    }
 }
 
 fn toggle_gizmos(mut r: ResMut<ShowGizmos>) {
@@ -323,129 +611,62 @@ fn last_enemy_dead(
     }
 }
 
-fn set_mission_status(mut mission_status: ResMut<NextState<MissionState>>) {
-    mission_status.set(MissionState::Ongoing);
-}
-
 fn nuke_touhou(
     mut commands: Commands,
     touhou_objects: Query<Entity, With<TouhouMarker>>,
     touhou_camera: Query<Entity, With<TouhouCamera>>,
 ) {
     for obj in &touhou_objects {
-        commands.entity(obj).try_despawn_recursive();
+        commands.entity(obj).try_despawn();
     }
 
     for obj in &touhou_camera {
-        commands.entity(obj).despawn_recursive();
+        commands.entity(obj).despawn();
     }
 }
 
 const N_SHIP_TEXTURES: usize = 3;
 
-fn load_player_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let atlas =
-        TextureAtlasLayout::from_grid(UVec2::splat(64), N_SHIP_TEXTURES as u32, 2, None, None);
-
-    commands.insert_resource(PlayerAssets {
-        dead: asset_server.load("dead.png"),
-        alive_sheet: asset_server.load("Xcom_hud/Playerrocket1-sheet.png"),
-        atlas_layout: atlas.clone(),
-        atlas_handle: asset_server.add(atlas),
-    })
-}
-
-fn load_touhou_assets(mut commands: Commands, asset_server: Res<AssetServer>) {
-    commands.insert_resource(TouhouAssets {
-        redgirl_sheet: asset_server.load("Enemies/Girlanimation1-sheet.png"),
-        redgirl_layout: asset_server.add(TextureAtlasLayout::from_grid(
-            UVec2::splat(64),
-            3,
-            1,
-            None,
-            None,
-        )),
-        bullet1: asset_server.load("bullets/bullet1.png"),
-        kaguya_sheet: asset_server.load("Enemies/Moongirl1-sheet.png"),
-        kaguya_layout: asset_server.add(TextureAtlasLayout::from_grid(
-            UVec2::splat(128),
-            5,
-            1,
-            None,
-            None,
-        )),
-        tentacle: asset_server.load("Enemies/Babyalien.png"),
-        lizard_sheet: asset_server.load("Enemies/Lizard1-sheet.png"),
-        lizard_layout: asset_server.add(TextureAtlasLayout::from_grid(
-            UVec2::splat(64),
-            3,
-            1,
-            None,
-            None,
-        )),
-        rocket: asset_server.load("Xcom_hud/rocket2.png"),
-
-        moongirl_bullet_sheet: asset_server.load("bullets/Moonbullet1-sheet.png"),
-        moongirl_layout: asset_server.add(TextureAtlasLayout::from_grid(
-            UVec2::splat(32),
-            5,
-            1,
-            None,
-            None,
-        )),
-
-        lizard_bullet_sheet: asset_server.load("bullets/Lizard1_bullet-sheet.png"),
-        lizard_bullet_layout: asset_server.add(TextureAtlasLayout::from_grid(
-            UVec2::splat(32),
-            3,
-            1,
-            None,
-            None,
-        )),
-
-        girl_bullet_sheet: asset_server.load("bullets/Girlbullet.png"),
-        girl_bullet_layout: asset_server.add(TextureAtlasLayout::from_grid(
-            UVec2::splat(32),
-            2,
-            1,
-            None,
-            None,
-        )),
-
-        girl_bullet2_sheet: asset_server.load("bullets/Girlbullet2.png"),
-        girl_bullet2_layout: asset_server.add(TextureAtlasLayout::from_grid(
-            UVec2::splat(32),
-            2,
-            1,
-            None,
-            None,
-        )),
-    })
-}
-
-#[derive(Resource)]
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
 pub struct TouhouAssets {
-    redgirl_sheet: Handle<Image>,
-    redgirl_layout: Handle<TextureAtlasLayout>,
-    kaguya_sheet: Handle<Image>,
-    kaguya_layout: Handle<TextureAtlasLayout>,
+    #[dependency]
+    player_sheet: Handle<Image>,
+    player_layout: Handle<TextureAtlasLayout>,
+    #[dependency]
+    player_dead: Handle<Image>,
+    #[dependency]
+    background: Handle<Image>,
+    #[dependency]
     bullet1: Handle<Image>,
-    tentacle: Handle<Image>,
-    lizard_sheet: Handle<Image>,
-    lizard_layout: Handle<TextureAtlasLayout>,
+    #[dependency]
     rocket: Handle<Image>,
-
-    moongirl_bullet_sheet: Handle<Image>,
-    moongirl_layout: Handle<TextureAtlasLayout>,
-
-    lizard_bullet_sheet: Handle<Image>,
-    lizard_bullet_layout: Handle<TextureAtlasLayout>,
-
-    girl_bullet_sheet: Handle<Image>,
-    girl_bullet_layout: Handle<TextureAtlasLayout>,
-
-    girl_bullet2_sheet: Handle<Image>,
-    girl_bullet2_layout: Handle<TextureAtlasLayout>,
+    #[dependency]
+    flagship_boss: Handle<BossDefinition>,
+}
+
+impl FromWorld for TouhouAssets {
+    fn from_world(world: &mut World) -> Self {
+        use crate::util::make_nearest;
+        let assets = world.resource::<AssetServer>();
+        Self {
+            player_sheet: assets
+                .load_with_settings("images/ui/Playerrocket1-sheet.png", make_nearest),
+            player_layout: assets.add(TextureAtlasLayout::from_grid(
+                UVec2::splat(64),
+                N_SHIP_TEXTURES as u32,
+                2,
+                None,
+                None,
+            )),
+            player_dead: assets.load_with_settings("images/ui/Ghost.png", make_nearest),
+            background: assets
+                .load_with_settings("images/level/T_BackgroundTiling.png", make_nearest),
+            bullet1: assets.load_with_settings("images/entities/Messil.png", make_nearest),
+            rocket: assets.load_with_settings("images/ui/rocket2.png", make_nearest),
+            flagship_boss: assets.load("bosses/flagship.boss.ron"),
+        }
+    }
 }
 
 fn player_dead(life: Option<PlayerQ<&Life>>) -> bool {
@@ -454,24 +675,25 @@ fn player_dead(life: Option<PlayerQ<&Life>>) -> bool {
 
 fn on_damage(
     mut commands: Commands,
-    player: Option<Single<(Entity, &mut Sprite), (PlayerFilter, Changed<Life>)>>,
+    player: Option<Single<Entity, (PlayerFilter, Changed<Life>)>>,
 ) {
-    let Some((ent, mut sprite)) = player.map(|p| p.into_inner()) else {
+    let Some(ent) = player else {
         return;
     };
 
     commands
-        .entity(ent)
+        .entity(*ent)
         .insert(Invulnerability(Timer::from_seconds(3.0, TimerMode::Once)));
 }
 
 fn on_death(
     player: PlayerQ<&mut Sprite>,
-    player_assets: Res<PlayerAssets>,
+    assets: Res<TouhouAssets>,
     mut mission_status: ResMut<NextState<MissionState>>,
 ) {
     let mut sprite = player.into_inner();
-    sprite.image = player_assets.dead.clone();
+    sprite.image = assets.player_dead.clone();
+    sprite.texture_atlas = None;
     mission_status.set(MissionState::Fail);
 }
 
@@ -484,7 +706,7 @@ fn update_invulnerability(
         timer.0.tick(time.delta());
 
         if timer.0.finished() {
-            commands.entity(ent).remove::<(Invulnerability)>();
+            commands.entity(ent).remove::<Invulnerability>();
         }
     }
 }
@@ -492,7 +714,11 @@ fn update_invulnerability(
 fn make_game_camera(mut commands: Commands) {
     commands.spawn((
         Camera2d,
-        OrthographicProjection {
+        Camera {
+            order: 1,
+            ..default()
+        },
+        Projection::from(OrthographicProjection {
             near: -1000.0,
             far: 1000.0,
             viewport_origin: Vec2::splat(0.5),
@@ -502,7 +728,8 @@ fn make_game_camera(mut commands: Commands) {
             },
             scale: 1.0,
             area: Rect::new(0.0, 0.0, 800.0, 600.0),
-        },
+        }),
+        RenderLayers::layer(TOUHOU_LAYER),
         TouhouCamera,
     ));
 }
@@ -510,34 +737,19 @@ fn make_game_camera(mut commands: Commands) {
 #[derive(Component)]
 struct ScrollingBg;
 
-fn make_bg(mut commands: Commands, params: Res<MissionParams>, asset_server: Res<AssetServer>) {
-    let map = params.map;
-
-    let img = asset_server.load(match params.map {
-        Map::Day => "Bakground/Sky1Side.png",
-        Map::Night => "Bakground/Nightsky.png",
-        Map::Dusk => "Bakground/sunset.png",
-        Map::Moon => "Bakground/moon.png",
-    });
-
-    let img = if params.enemy == Enemies::MoonGirl {
-        asset_server.load("Bakground/moon.png")
-    } else {
-        img
-    };
-
+fn make_bg(mut commands: Commands, assets: Res<TouhouAssets>) {
     commands.spawn((
         Sprite {
-            image: img,
+            image: assets.background.clone(),
             custom_size: Some(Vec2::new(1920.0, 1080.0)),
             image_mode: SpriteImageMode::Tiled {
                 tile_x: true,
-                tile_y: false,
-                stretch_value: 1080.0 / 64.0,
+                tile_y: true,
+                stretch_value: 2.0,
             },
             rect: Some(Rect {
                 min: Vec2::new(0.0, 0.0),
-                max: Vec2::new(16.0 / 9.0 * 64.0, 64.0),
+                max: Vec2::new(16.0 / 9.0 * 512.0, 512.0),
             }),
             ..Default::default()
         },
@@ -547,11 +759,11 @@ fn make_bg(mut commands: Commands, params: Res<MissionParams>, asset_server: Res
     ));
 }
 
-fn scroll_map(time: Res<Time>, mut map: Single<&mut Sprite, With<ScrollingBg>>) {
+fn scroll_map(time: Res<Time>, map: Single<&mut Sprite, With<ScrollingBg>>) {
     let mut sprite = map.into_inner();
 
     let Some(rect) = sprite.rect.as_mut() else {
-        panic!("wtf");
+        return;
     };
 
     let scroll_value = time.delta_secs() * 8.0;
@@ -563,21 +775,20 @@ fn scroll_map(time: Res<Time>, mut map: Single<&mut Sprite, With<ScrollingBg>>)
 fn animate_player(
     player: Option<Single<(&mut Sprite, Option<&Invulnerability>), PlayerFilter>>,
     time: Res<Time>,
-    player_assets: Res<PlayerAssets>,
     mut animation_timer: Local<Option<Timer>>,
     mut inverted: Local<bool>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input: Res<TouhouInput>,
 ) {
     let Some((mut sprite, invuln)) = player.map(Single::into_inner) else {
         return;
     };
 
-    let up = keyboard_input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) as i32;
-    let down = keyboard_input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) as i32;
-
-    let direction = up - down;
+    let direction = input.movement().y as i32;
 
-    let atlas: &mut TextureAtlas = sprite.texture_atlas.as_mut().unwrap();
+    // the player is dead and no longer uses the sheet
+    let Some(atlas) = sprite.texture_atlas.as_mut() else {
+        return;
+    };
 
     atlas.index = match direction {
         1 => 0,
@@ -587,7 +798,7 @@ fn animate_player(
     };
 
     if invuln.is_some() {
-        let mut animation_timer =
+        let animation_timer =
             animation_timer.get_or_insert(Timer::from_seconds(0.05, TimerMode::Repeating));
 
         animation_timer.tick(time.delta());
@@ -596,9 +807,9 @@ fn animate_player(
             animation_timer.reset();
 
             *inverted = !*inverted;
-            // if inverted: +2 or +3 depending on how big the atlas is
-            atlas.index += *inverted as usize * N_SHIP_TEXTURES;
         }
+        // if inverted: +2 or +3 depending on how big the atlas is
+        atlas.index += *inverted as usize * N_SHIP_TEXTURES;
     }
 }
 
@@ -614,6 +825,9 @@ pub struct Player {
     markers: (PlayerMarker, TouhouMarker),
     ammo: Ammo,
     speed: Speed,
+    score: Score,
+    graze: Graze,
+    bombs: Bombs,
 }
 
 #[derive(Component, Deref, DerefMut)]
@@ -622,6 +836,22 @@ pub struct Life(usize);
 #[derive(Component, Deref, DerefMut, Default)]
 pub struct Ammo(u32);
 
+#[derive(Component, Deref, DerefMut, Default)]
+pub struct Score(u64);
+
+#[derive(Component, Deref, DerefMut)]
+pub struct Bombs(u32);
+
+impl Default for Bombs {
+    fn default() -> Self {
+        Bombs(3)
+    }
+}
+
+/// How many bullets the player has grazed this encounter.
+#[derive(Component, Deref, DerefMut, Default)]
+pub struct Graze(u32);
+
 impl Default for Life {
     fn default() -> Self {
         Life(1)
@@ -639,21 +869,25 @@ pub fn create_gameplay_rect(mut commands: Commands) {
     });
 }
 
-pub fn spawn_player(mut commands: Commands, player_assets: Res<PlayerAssets>) {
+pub fn spawn_player(
+    mut commands: Commands,
+    assets: Res<TouhouAssets>,
+    difficulty: Res<Difficulty>,
+) {
     commands.spawn(Player {
         sprite: Sprite {
             custom_size: Some(Vec2::new(100.0, 100.0)),
-            image: player_assets.alive_sheet.clone(),
+            image: assets.player_sheet.clone(),
             anchor: bevy::sprite::Anchor::Custom(Vec2::from((0.1, -0.05))),
             texture_atlas: Some(TextureAtlas {
-                layout: player_assets.atlas_handle.clone(),
+                layout: assets.player_layout.clone(),
                 index: 0,
             }),
             ..Default::default()
         },
         transform: Transform::from_xyz(-1920.0 / 3.0, 0.0, -0.5),
         collider: Collider { radius: 7.5 },
-        lives: Life(3),
+        lives: Life(difficulty.lives()),
         speed: Speed(6.5),
         ammo: Ammo(1000),
         ..Default::default()
@@ -663,10 +897,10 @@ pub fn spawn_player(mut commands: Commands, player_assets: Res<PlayerAssets>) {
 fn draw_gizmos(
     mut gizmos: Gizmos,
     area: Res<GameplayRect>,
-    enabled: ResMut<ShowGizmos>,
+    enabled: Res<ShowGizmos>,
     colliders: Query<(&Transform, &Collider)>,
 ) {
-    if enabled.enabled || true {
+    if !enabled.enabled {
         return;
     }
     use bevy::color::palettes::css::RED;
@@ -689,38 +923,86 @@ fn draw_gizmos(
     }
 }
 
+fn set_focus(mut commands: Commands, player: PlayerQ<Entity>, input: Res<TouhouInput>) {
+    let mut player = commands.entity(*player);
+
+    if input.pressed(InputFrame::FOCUS) {
+        player.insert(Focused);
+    } else {
+        player.remove::<Focused>();
+    }
+}
+
+/// A white dot with a red rim, drawn above the bullets.
+fn spawn_hitbox(
+    mut commands: Commands,
+    player: Single<Entity, PlayerFilter>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    // not our own `Circle`
+    use bevy::{color::palettes::css::RED, math::primitives::Circle as Disc};
+
+    let rim = (
+        TouhouMarker,
+        Mesh2d(meshes.add(Annulus::new(1.0, 1.3))),
+        MeshMaterial2d(materials.add(Color::from(RED))),
+    );
+
+    commands.entity(*player).with_child((
+        TouhouMarker,
+        Hitbox,
+        Mesh2d(meshes.add(Disc::new(1.0))),
+        MeshMaterial2d(materials.add(Color::WHITE)),
+        Transform::from_xyz(0.0, 0.0, 1.0),
+        Visibility::Hidden,
+        children![rim],
+    ));
+}
+
+fn show_hitbox(
+    player: Option<Single<(&Collider, Has<Focused>), PlayerFilter>>,
+    hitbox: Option<Single<(&mut Transform, &mut Visibility), With<Hitbox>>>,
+) {
+    let (Some(player), Some(hitbox)) = (player, hitbox) else {
+        return;
+    };
+    let (collider, focused) = player.into_inner();
+    let (mut trans, mut visibility) = hitbox.into_inner();
+
+    trans.scale = Vec3::splat(collider.radius);
+    *visibility = if focused {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+}
+
 fn do_movement(
-    time: Res<Time>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    input: Res<TouhouInput>,
     area: Res<GameplayRect>,
-    asset_server: ResMut<AssetServer>,
-    mut player_info: Single<
+    player_info: Single<
         (
             &Speed,
             &mut Transform,
             &Collider,
-            &mut Sprite,
             Option<&AltFire>,
+            Has<Focused>,
         ),
         With<PlayerMarker>,
     >,
 ) {
-    let (speed, mut trans, mut collider, mut sprite, alt_fire) = player_info.into_inner();
-    let up = keyboard_input.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) as i32 as f32;
-    let down = keyboard_input.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) as i32 as f32;
-    let left = keyboard_input.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) as i32 as f32;
-    let right = keyboard_input.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) as i32 as f32;
-
-    let dy = up + -down;
-    let dx = right + -left;
-
+    let (speed, mut trans, collider, alt_fire, focused) = player_info.into_inner();
     let mut speed = **speed;
 
     if alt_fire.is_some() {
-        speed = speed / 2.0;
+        speed /= 2.0;
+    }
+    if focused {
+        speed *= FOCUS_SPEED;
     }
 
-    let wishdir = Vec3::new(dx, dy, 0.0).normalize_or_zero() * speed;
+    let wishdir = input.movement().extend(0.0).normalize_or_zero() * speed;
 
     let new_pos = (trans.translation + wishdir).xy();
 
@@ -0,0 +1,157 @@
+//! Bullet stress test, only in dev builds. Press F9 in the open world to jump straight
+//! into the encounter with an invulnerable player and the arena kept topped up with
+//! [`BENCHMARK_BULLETS`] extra bullets. Press F9 again to leave.
+
+use std::f32::consts::TAU;
+
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bullet::{BulletBundle, BulletCommandExt, EnemyBullets, NormalBullet};
+use rand::Rng;
+use spatial_hash::BulletGrid;
+
+use super::*;
+
+const TOGGLE_KEY: KeyCode = KeyCode::F9;
+const BENCHMARK_BULLETS: usize = 10_000;
+/// How long the player stays invulnerable, i.e. for as long as anyone would watch.
+const BENCHMARK_SECS: f32 = 60.0 * 60.0;
+
+pub fn benchmark_plugin(app: &mut App) {
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+    }
+
+    app.init_resource::<BulletBenchmark>()
+        .add_systems(
+            Update,
+            start_benchmark
+                .run_if(in_state(GameState::OpenWorld).and(input_just_pressed(TOGGLE_KEY))),
+        )
+        .add_systems(
+            Update,
+            stop_benchmark.run_if(
+                in_state(GameState::Touhou)
+                    .and(benchmark_running)
+                    .and(input_just_pressed(TOGGLE_KEY)),
+            ),
+        )
+        .add_systems(
+            OnEnter(GameState::Touhou),
+            (make_player_invulnerable, spawn_benchmark_overlay)
+                .after(TouhouSets::EnterTouhou)
+                .run_if(benchmark_running),
+        )
+        .add_systems(
+            FixedUpdate,
+            top_up_bullets
+                .in_set(TouhouSets::Gameplay)
+                .run_if(benchmark_running),
+        )
+        .add_systems(Update, update_benchmark_overlay.run_if(benchmark_running))
+        .add_systems(
+            OnExit(GameState::Touhou),
+            |mut benchmark: ResMut<BulletBenchmark>| benchmark.running = false,
+        );
+}
+
+#[derive(Resource, Default)]
+struct BulletBenchmark {
+    running: bool,
+}
+
+#[derive(Component)]
+struct BenchmarkOverlay;
+
+fn benchmark_running(benchmark: Res<BulletBenchmark>) -> bool {
+    benchmark.running
+}
+
+fn start_benchmark(
+    mut benchmark: ResMut<BulletBenchmark>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    benchmark.running = true;
+    next_state.set(GameState::Touhou);
+}
+
+fn stop_benchmark(mut next_state: ResMut<NextState<GameState>>) {
+    next_state.set(GameState::OpenWorld);
+}
+
+fn make_player_invulnerable(mut commands: Commands, player: Single<Entity, PlayerFilter>) {
+    commands
+        .entity(*player)
+        .insert(Invulnerability(Timer::from_seconds(
+            BENCHMARK_SECS,
+            TimerMode::Once,
+        )));
+}
+
+/// Keeps the bullet count at [`BENCHMARK_BULLETS`], replacing the ones that leave the arena.
+fn top_up_bullets(
+    mut commands: Commands,
+    assets: Res<TouhouAssets>,
+    rect: Res<GameplayRect>,
+    bullets: Query<(), EnemyBullets>,
+) {
+    let missing = BENCHMARK_BULLETS.saturating_sub(bullets.iter().count());
+    let mut rng = rand::rng();
+
+    for _ in 0..missing {
+        let pos = Vec2::new(
+            rng.random_range(rect.rect.min.x..rect.rect.max.x),
+            rng.random_range(rect.rect.min.y..rect.rect.max.y),
+        );
+        let velocity = Vec2::from_angle(rng.random_range(0.0..TAU)) * rng.random_range(0.5..2.0);
+
+        commands
+            .spawn(BulletBundle {
+                transform: Transform::from_translation(pos.extend(0.0)),
+                collider: Collider { radius: 5.0 },
+                sprite: Sprite {
+                    image: assets.bullet1.clone(),
+                    custom_size: Some(Vec2::splat(10.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .add_bullet(NormalBullet { velocity });
+    }
+}
+
+fn spawn_benchmark_overlay(mut commands: Commands, ui_assets: Res<UIAssets>) {
+    commands.spawn((
+        TouhouMarker,
+        BenchmarkOverlay,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.0),
+            left: Val::Px(8.0),
+            ..default()
+        },
+        Text::default(),
+        TextFont {
+            font: ui_assets.font.clone(),
+            font_size: 20.0,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+    ));
+}
+
+fn update_benchmark_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    grid: Res<BulletGrid>,
+    mut overlay: Single<&mut Text, With<BenchmarkOverlay>>,
+) {
+    let Some(frame_time) = diagnostics.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else {
+        return;
+    };
+    let average = frame_time.average().unwrap_or_default();
+    let worst = frame_time.values().copied().fold(0.0, f64::max);
+
+    overlay.0 = format!(
+        "bullets: {}\nframe time: {average:.2} ms avg, {worst:.2} ms worst",
+        grid.enemy_bullets.len() + grid.player_bullets.len(),
+    );
+}
@@ -0,0 +1,418 @@
+//! Bosses are described in `assets/bosses/*.boss.ron` files, so patterns can be
+//! authored without recompiling. On native dev builds the files hot-reload, and a
+//! running encounter restarts with the new definition.
+//!
+//! Angles and rotation speeds are written in degrees and degrees/s, velocities in
+//! units per fixed tick, times in seconds.
+
+use std::collections::HashMap;
+
+use bevy::asset::{AssetLoader, LoadContext, io::Reader};
+use serde::Deserialize;
+use thiserror::Error;
+
+use super::{
+    bullet::{BulletBundle, DelayedBullet, Target},
+    enemy::{Animation, BulletSpawner},
+    *,
+};
+
+pub fn boss_plugin(app: &mut App) {
+    app.init_asset::<BossDefinition>()
+        .init_asset_loader::<BossDefinitionLoader>();
+}
+
+/// A boss with all of its sprites and spellcards, ready to be spawned.
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct BossDefinition {
+    pub name: String,
+    pub sprite: Sprite,
+    pub animation: Animation,
+    pub position: Vec2,
+    pub rotation: f32,
+    pub collider_radius: f32,
+    pub health: u32,
+    pub movement: BossMovement,
+    pub spellcards: Vec<SpellcardDefinition>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpellcardDefinition {
+    pub name: String,
+    pub start_time: f32,
+    pub end_time: f32,
+    pub emitters: Vec<EmitterDefinition>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EmitterDefinition {
+    pub interval: f32,
+    pub pattern: EmitterPattern,
+    pub spawner: BulletSpawner,
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub enum BossMovement {
+    #[default]
+    Still,
+    /// Glides to `first_target`, then to random spots in the right half of the arena.
+    Random {
+        first_target: (f32, f32),
+        move_time: f32,
+        interval: f32,
+    },
+}
+
+/// Which emitter component an emitter gets, see the matching `*Emitter` in `enemy.rs`.
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub enum EmitterPattern {
+    CircularAimed {
+        offset: f32,
+        count: usize,
+    },
+    CircularHoming {
+        offset: f32,
+        count: usize,
+    },
+    CircularWave {
+        offset: f32,
+        count: usize,
+        rotation_speed: f32,
+    },
+    Tentacle {
+        offset: f32,
+        count: usize,
+    },
+    Flood {
+        spray: f32,
+    },
+    Spray {
+        spray_width: f32,
+        firing_time: f32,
+        firing_speed: f32,
+    },
+    RotatingSpray {
+        spray_width: f32,
+        firing_time: f32,
+        firing_speed: f32,
+        rotation_speed: f32,
+        spray_count: usize,
+    },
+    Divisive {
+        columns: u64,
+        rows: u64,
+    },
+    /// Beams from the boss, telegraphed by a thin line for `warning_time` before they
+    /// fire for `firing_time`. While firing they turn at `rotation_speed` and swing
+    /// `sweep` degrees to either side every `sweep_time`. Doesn't use the spawner.
+    Laser {
+        count: usize,
+        length: f32,
+        width: f32,
+        warning_time: f32,
+        firing_time: f32,
+        #[serde(default)]
+        angle: f32,
+        #[serde(default)]
+        spread: f32,
+        #[serde(default)]
+        rotation_speed: f32,
+        #[serde(default)]
+        sweep: f32,
+        #[serde(default)]
+        sweep_time: f32,
+        #[serde(default)]
+        aimed: bool,
+    },
+}
+
+#[derive(Deserialize)]
+struct BossFile {
+    name: String,
+    sprite: SpriteFile,
+    position: (f32, f32),
+    #[serde(default)]
+    rotation: f32,
+    collider_radius: f32,
+    health: u32,
+    #[serde(default)]
+    movement: BossMovement,
+    bullets: HashMap<String, BulletFile>,
+    spellcards: Vec<SpellcardFile>,
+}
+
+#[derive(Deserialize)]
+struct SpriteFile {
+    image: String,
+    size: f32,
+    #[serde(default)]
+    atlas: Option<AtlasFile>,
+}
+
+#[derive(Deserialize)]
+struct AtlasFile {
+    tile_size: u32,
+    columns: u32,
+    rows: u32,
+    frames: usize,
+    frame_time: f32,
+}
+
+#[derive(Deserialize)]
+struct BulletFile {
+    sprite: SpriteFile,
+    radius: f32,
+}
+
+#[derive(Deserialize)]
+struct SpellcardFile {
+    name: String,
+    start: f32,
+    end: f32,
+    emitters: Vec<EmitterFile>,
+}
+
+#[derive(Deserialize)]
+struct EmitterFile {
+    interval: f32,
+    pattern: EmitterPattern,
+    #[serde(default)]
+    spawner: Option<SpawnerFile>,
+}
+
+#[derive(Deserialize)]
+struct SpawnerFile {
+    bullet: String,
+    #[serde(default)]
+    normal: Option<(f32, f32)>,
+    #[serde(default)]
+    rotation: Option<RotationFile>,
+    #[serde(default)]
+    stutter: Option<StutterFile>,
+    #[serde(default)]
+    homing: Option<HomingFile>,
+    #[serde(default)]
+    wave: Option<WaveFile>,
+    #[serde(default)]
+    delayed: Option<Box<DelayedFile>>,
+}
+
+#[derive(Deserialize)]
+struct RotationFile {
+    #[serde(default)]
+    origin: (f32, f32),
+    speed: f32,
+}
+
+#[derive(Deserialize)]
+struct StutterFile {
+    wait_time: f32,
+    initial_velocity: (f32, f32),
+}
+
+#[derive(Deserialize)]
+struct HomingFile {
+    seeking_time: f32,
+    rotation_speed: f32,
+    target: Target,
+}
+
+#[derive(Deserialize)]
+struct WaveFile {
+    sine_mod: f32,
+    true_velocity: (f32, f32),
+}
+
+#[derive(Deserialize)]
+struct DelayedFile {
+    delay: f32,
+    spawner: SpawnerFile,
+}
+
+#[derive(Default)]
+struct BossDefinitionLoader;
+
+#[derive(Debug, Error)]
+enum BossDefinitionLoaderError {
+    #[error("could not read boss file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("could not parse boss file: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("spawner uses bullet `{0}`, which isn't in `bullets`")]
+    UnknownBullet(String),
+    #[error("emitter in `{0}` fires bullets but has no spawner")]
+    MissingSpawner(String),
+}
+
+impl AssetLoader for BossDefinitionLoader {
+    type Asset = BossDefinition;
+    type Settings = ();
+    type Error = BossDefinitionLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: BossFile = ron::de::from_bytes(&bytes)?;
+
+        let (sprite, animation) = load_sprite(load_context, "boss", &file.sprite);
+
+        let bullets = file
+            .bullets
+            .iter()
+            .map(|(name, bullet)| {
+                let (sprite, animation) = load_sprite(load_context, name, &bullet.sprite);
+                let bundle = BulletBundle {
+                    collider: Collider {
+                        radius: bullet.radius,
+                    },
+                    sprite,
+                    animation,
+                    ..Default::default()
+                };
+                (name.clone(), bundle)
+            })
+            .collect::<HashMap<_, _>>();
+
+        let spellcards = file
+            .spellcards
+            .iter()
+            .map(|card| {
+                let emitters = card
+                    .emitters
+                    .iter()
+                    .map(|emitter| {
+                        let spawner = match (&emitter.spawner, emitter.pattern) {
+                            (Some(spawner), _) => build_spawner(&bullets, spawner)?,
+                            (None, EmitterPattern::Laser { .. }) => BulletSpawner::default(),
+                            (None, _) => {
+                                return Err(BossDefinitionLoaderError::MissingSpawner(
+                                    card.name.clone(),
+                                ));
+                            }
+                        };
+
+                        Ok(EmitterDefinition {
+                            interval: emitter.interval,
+                            pattern: emitter.pattern,
+                            spawner,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, BossDefinitionLoaderError>>()?;
+
+                Ok(SpellcardDefinition {
+                    name: card.name.clone(),
+                    start_time: card.start,
+                    end_time: card.end,
+                    emitters,
+                })
+            })
+            .collect::<Result<Vec<_>, BossDefinitionLoaderError>>()?;
+
+        Ok(BossDefinition {
+            name: file.name,
+            sprite,
+            animation,
+            position: file.position.into(),
+            rotation: file.rotation.to_radians(),
+            collider_radius: file.collider_radius,
+            health: file.health,
+            movement: file.movement,
+            spellcards,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["boss.ron"]
+    }
+}
+
+fn load_sprite(
+    load_context: &mut LoadContext<'_>,
+    label: &str,
+    file: &SpriteFile,
+) -> (Sprite, Animation) {
+    use crate::util::make_nearest;
+
+    let image = load_context
+        .loader()
+        .with_settings(make_nearest)
+        .load(&file.image);
+
+    let Some(atlas) = &file.atlas else {
+        return (
+            Sprite {
+                image,
+                custom_size: Some(Vec2::splat(file.size)),
+                ..Default::default()
+            },
+            Animation::default(),
+        );
+    };
+
+    let layout = load_context.add_labeled_asset(
+        format!("{label}_layout"),
+        TextureAtlasLayout::from_grid(
+            UVec2::splat(atlas.tile_size),
+            atlas.columns,
+            atlas.rows,
+            None,
+            None,
+        ),
+    );
+
+    (
+        Sprite {
+            image,
+            custom_size: Some(Vec2::splat(file.size)),
+            texture_atlas: Some(TextureAtlas { layout, index: 0 }),
+            ..Default::default()
+        },
+        Animation::new(atlas.frame_time, atlas.frames, 0),
+    )
+}
+
+fn build_spawner(
+    bullets: &HashMap<String, BulletBundle>,
+    file: &SpawnerFile,
+) -> Result<BulletSpawner, BossDefinitionLoaderError> {
+    let bullet = bullets
+        .get(&file.bullet)
+        .cloned()
+        .ok_or_else(|| BossDefinitionLoaderError::UnknownBullet(file.bullet.clone()))?;
+
+    let mut spawner = BulletSpawner::new(bullet);
+
+    if let Some(velocity) = file.normal {
+        spawner = spawner.normal(velocity.into());
+    }
+    if let Some(rotation) = &file.rotation {
+        spawner = spawner.rotation(rotation.origin.into(), rotation.speed.to_radians());
+    }
+    if let Some(stutter) = &file.stutter {
+        spawner = spawner.stutter(stutter.wait_time, stutter.initial_velocity.into(), false);
+    }
+    if let Some(homing) = &file.homing {
+        spawner = spawner.homing(
+            homing.seeking_time,
+            homing.rotation_speed.to_radians(),
+            homing.target,
+        );
+    }
+    if let Some(wave) = &file.wave {
+        spawner = spawner.wave(wave.sine_mod, wave.true_velocity.into());
+    }
+    if let Some(delayed) = &file.delayed {
+        spawner = spawner.delayed(DelayedBullet {
+            bullet: build_spawner(bullets, &delayed.spawner)?,
+            delay: delayed.delay,
+            deployed: false,
+        });
+    }
+
+    Ok(spawner)
+}
@@ -0,0 +1,285 @@
+//! The boss's health and spellcards along the top of the screen, and the bonus for
+//! capturing a spellcard, i.e. getting through it without losing a life or bombing.
+
+use boss::SpellcardDefinition;
+use bullet::{BombUsed, LifeLost};
+use enemy::EncounterTime;
+use practice::{Practice, is_practiced};
+
+use super::*;
+
+const CAPTURE_BONUS: u64 = 100_000;
+const CAPTURE_BANNER_SECS: f32 = 3.0;
+
+pub fn boss_hud_plugin(app: &mut App) {
+    app.init_resource::<CardAttempts>()
+        .add_systems(
+            OnEnter(GameState::Touhou),
+            (spawn_boss_hud, |mut attempts: ResMut<CardAttempts>| {
+                attempts.clean.clear()
+            })
+                .in_set(TouhouSets::EnterTouhou),
+        )
+        .add_systems(
+            FixedPostUpdate,
+            track_captures
+                .after(bullet::process_player_hits)
+                .after(enemy_dead)
+                .in_set(TouhouSets::Gameplay),
+        )
+        .add_systems(
+            Update,
+            (update_boss_hud, fade_capture_banner).in_set(TouhouSets::Gameplay),
+        );
+}
+
+/// Whether each spellcard has gone clean so far, for the ones that are active.
+#[derive(Resource, Default)]
+struct CardAttempts {
+    clean: Vec<Option<bool>>,
+}
+
+#[derive(Component)]
+struct BossHealthFill;
+#[derive(Component)]
+struct SpellcardName;
+#[derive(Component)]
+struct SpellcardTimer;
+#[derive(Component, Deref, DerefMut)]
+struct CaptureBanner(Timer);
+
+/// Cards left out of a practice run never count as active.
+fn active(card: &SpellcardDefinition, time: f32, practice: Option<&Practice>) -> bool {
+    is_practiced(practice, &card.name) && card.start_time < time && time < card.end_time
+}
+
+/// The card the HUD shows. Cards can overlap, so it's whichever runs out first.
+fn current_card<'a>(
+    boss: &'a BossDefinition,
+    time: f32,
+    practice: Option<&Practice>,
+) -> Option<&'a SpellcardDefinition> {
+    boss.spellcards
+        .iter()
+        .filter(|card| active(card, time, practice))
+        .min_by(|a, b| a.end_time.total_cmp(&b.end_time))
+}
+
+fn spawn_boss_hud(
+    mut commands: Commands,
+    ui_assets: Res<UIAssets>,
+    assets: Res<TouhouAssets>,
+    bosses: Res<Assets<BossDefinition>>,
+) {
+    let Some(boss) = bosses.get(&assets.flagship_boss) else {
+        return;
+    };
+    let font = TextFont {
+        font: ui_assets.font.clone(),
+        font_size: 28.0,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            TouhouMarker,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Vw(60.0),
+                left: Val::Vw(20.0),
+                top: Val::Px(0.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+            ZIndex(1),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    justify_content: JustifyContent::SpaceBetween,
+                    ..default()
+                },
+                children![
+                    (
+                        Text::new(boss.name.clone()),
+                        SpellcardName,
+                        font.clone(),
+                        TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                    ),
+                    (
+                        Text::default(),
+                        SpellcardTimer,
+                        font.clone(),
+                        TextColor(Color::srgb(0.9, 0.9, 0.5)),
+                    ),
+                ],
+            ));
+
+            // one segment per spellcard, so the player can tell how many cards' worth
+            // of health are left
+            let segments = boss.spellcards.len().max(1);
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Px(16.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.2, 0.05, 0.05)),
+                ))
+                .with_children(|bar| {
+                    bar.spawn((
+                        BossHealthFill,
+                        Node {
+                            position_type: PositionType::Absolute,
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.9, 0.15, 0.15)),
+                    ));
+                    for i in 1..segments {
+                        bar.spawn((
+                            Node {
+                                position_type: PositionType::Absolute,
+                                left: Val::Percent(100.0 * i as f32 / segments as f32),
+                                width: Val::Px(3.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::BLACK),
+                        ));
+                    }
+                });
+        });
+}
+
+fn update_boss_hud(
+    assets: Res<TouhouAssets>,
+    bosses: Res<Assets<BossDefinition>>,
+    enc_time: Res<EncounterTime>,
+    practice: Option<Res<Practice>>,
+    enemy_hp: Option<Single<&Health, With<EnemyMarker>>>,
+    mut fill: Single<&mut Node, With<BossHealthFill>>,
+    mut name: Single<&mut Text, (With<SpellcardName>, Without<SpellcardTimer>)>,
+    mut timer: Single<&mut Text, (With<SpellcardTimer>, Without<SpellcardName>)>,
+) {
+    let Some(boss) = bosses.get(&assets.flagship_boss) else {
+        return;
+    };
+    let health = enemy_hp.map_or(0, |hp| ***hp);
+    fill.width = Val::Percent(100.0 * health as f32 / boss.health.max(1) as f32);
+
+    let time = enc_time.time.elapsed_secs();
+    match current_card(boss, time, practice.as_deref()) {
+        Some(card) => {
+            name.0.clone_from(&card.name);
+            timer.0 = format!("{:.1}", card.end_time - time);
+        }
+        None => {
+            name.0.clone_from(&boss.name);
+            timer.0.clear();
+        }
+    }
+}
+
+/// A card is over when its time runs out or the boss goes down during it.
+fn track_captures(
+    mut commands: Commands,
+    assets: Res<TouhouAssets>,
+    bosses: Res<Assets<BossDefinition>>,
+    ui_assets: Res<UIAssets>,
+    enc_time: Res<EncounterTime>,
+    practice: Option<Res<Practice>>,
+    mut attempts: ResMut<CardAttempts>,
+    mut life_lost: EventReader<LifeLost>,
+    mut bomb_used: EventReader<BombUsed>,
+    enemies: Query<(), With<EnemyMarker>>,
+    player: Option<PlayerQ<&mut Score>>,
+) {
+    let Some(boss) = bosses.get(&assets.flagship_boss) else {
+        return;
+    };
+    let lives_lost = life_lost.read().count();
+    let bombs_used = bomb_used.read().count();
+    let missed = lives_lost + bombs_used > 0;
+    let boss_alive = !enemies.is_empty();
+    let time = enc_time.time.elapsed_secs();
+
+    attempts.clean.resize(boss.spellcards.len(), None);
+    let mut captured = vec![];
+
+    for (card, attempt) in boss.spellcards.iter().zip(&mut attempts.clean) {
+        let is_active = boss_alive && active(card, time, practice.as_deref());
+
+        match (*attempt, is_active) {
+            (None, true) => *attempt = Some(true),
+            (Some(clean), true) => *attempt = Some(clean && !missed),
+            (Some(clean), false) => {
+                if clean && !missed {
+                    captured.push(card.name.as_str());
+                }
+                *attempt = None;
+            }
+            (None, false) => {}
+        }
+    }
+
+    if captured.is_empty() {
+        return;
+    }
+    let bonus = CAPTURE_BONUS * captured.len() as u64;
+    if let Some(mut score) = player {
+        ***score += bonus;
+    }
+
+    commands.spawn((
+        TouhouMarker,
+        CaptureBanner(Timer::from_seconds(CAPTURE_BANNER_SECS, TimerMode::Once)),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Vw(100.0),
+            top: Val::Vh(15.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        ZIndex(1),
+        children![(
+            Text::new(format!(
+                "Spellcard captured: {}\n+{bonus}",
+                captured.join(", ")
+            )),
+            TextFont {
+                font: ui_assets.font.clone(),
+                font_size: 40.0,
+                ..default()
+            },
+            TextLayout::new_with_justify(JustifyText::Center),
+            TextColor(Color::srgb(0.9, 0.9, 0.5)),
+        )],
+    ));
+}
+
+fn fade_capture_banner(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut banners: Query<(Entity, &mut CaptureBanner, &Children)>,
+    mut colors: Query<&mut TextColor>,
+) {
+    for (ent, mut timer, children) in &mut banners {
+        timer.tick(time.delta());
+        if timer.finished() {
+            commands.entity(ent).despawn();
+            continue;
+        }
+
+        for &child in children {
+            if let Ok(mut color) = colors.get_mut(child) {
+                color.0.set_alpha(timer.fraction_remaining());
+            }
+        }
+    }
+}
@@ -6,11 +6,12 @@ use std::{
 
 use bevy::{
     color::palettes::css::{BLUE, RED},
-    ecs::query::QueryFilter,
-    input::common_conditions::input_pressed,
     time::Stopwatch,
 };
 use enemy::{Animation, BulletSpawner, EnemyMarker, Health};
+use input::{InputFrame, TouhouInput, input_held, input_tapped};
+use serde::Deserialize;
+use spatial_hash::{BulletGrid, rebuild_bullet_grid};
 
 use super::*;
 
@@ -20,45 +21,68 @@ pub struct PlayerBullets {
     cond: With<PlayerBullet>,
 }
 #[derive(QueryFilter)]
-struct EnemyBullets {
+pub struct EnemyBullets {
     marker: With<BulletMarker>,
     cond: Without<PlayerBullet>,
 }
 type Bullets = With<BulletMarker>;
 
 pub fn bullet_plugin(app: &mut App) {
-    app.add_event::<BulletHit>()
+    app.init_resource::<BulletGrid>()
+        .add_event::<BombUsed>()
+        .add_event::<LifeLost>()
+        .add_event::<BulletCancel>()
+        .add_event::<BulletHit>()
         .add_event::<PlayerHit>()
         .add_event::<EnemyHit>()
         .add_systems(
             FixedUpdate,
             (
                 (
-                    move_normal_bullets,
-                    move_rotating_bullets,
-                    move_homing_bullets,
-                    move_wave_bullets,
-                    move_stutter_bullets,
-                    resolve_delayed_bullets,
+                    (
+                        move_normal_bullets,
+                        move_rotating_bullets,
+                        move_homing_bullets,
+                        move_wave_bullets,
+                        move_stutter_bullets,
+                        resolve_delayed_bullets,
+                    )
+                        .chain(),
+                    rebuild_bullet_grid,
+                    (
+                        check_enemy_bullets,
+                        check_grazes,
+                        check_bullet_bullet,
+                        check_player_bullets,
+                    ),
                 )
                     .chain(),
-                check_enemy_bullets,
-                check_bullet_bullet,
-                check_player_bullets,
                 despawn_bullets,
-                fire_weapons.run_if(input_pressed(KeyCode::KeyZ)),
+                (move_point_items, collect_point_items).chain(),
+                fire_weapons.run_if(input_held(InputFrame::FIRE)),
+                use_bomb.run_if(input_tapped(InputFrame::BOMB)),
                 tick_bullets,
             )
-                .run_if(in_state(GameState::Touhou)),
+                .in_set(TouhouSets::Gameplay),
         )
+        .add_systems(Update, fade_bomb_flash.in_set(TouhouSets::Gameplay))
         .add_systems(
             FixedPreUpdate,
-            set_alt_fire.run_if(in_state(GameState::Touhou)),
+            set_alt_fire
+                .after(TouhouSets::Input)
+                .in_set(TouhouSets::Gameplay),
         )
         .add_systems(
             FixedPostUpdate,
             (bullet_bullet_hit, process_player_hits, process_enemy_hits)
-                .run_if(in_state(GameState::Touhou)),
+                .in_set(TouhouSets::Gameplay),
+        )
+        .add_systems(
+            FixedPostUpdate,
+            cancel_bullets
+                .after(process_enemy_hits)
+                .run_if(on_event::<BulletCancel>)
+                .in_set(TouhouSets::Gameplay),
         );
 }
 
@@ -68,10 +92,11 @@ fn make_machinegun(assets: &TouhouAssets) -> Weapon {
         ammo_cost: 1,
         bullet: BulletSpawner::new(BulletBundle {
             transform: Transform::from_xyz(0.0, 0.0, 0.0)
-                .with_rotation(Quat::from_rotation_z(PI / 2.0)),
+                .with_rotation(Quat::from_rotation_z(-PI / 2.0)),
             collider: Collider { radius: 6.0 },
             sprite: Sprite {
                 image: assets.bullet1.clone(),
+                custom_size: Some(Vec2::splat(24.0)),
                 ..Default::default()
             },
             ..Default::default()
@@ -80,6 +105,32 @@ fn make_machinegun(assets: &TouhouAssets) -> Weapon {
         salted: true,
         damage: 2,
         phasing: false,
+        piercing: false,
+    }
+}
+
+/// A rapid stream of thin bolts that pass through both bullets and enemies,
+/// damaging the boss for as long as they overlap it.
+fn make_laser_stream(assets: &TouhouAssets, damage: u32) -> Weapon {
+    Weapon {
+        timer: Timer::new(Duration::from_secs_f32(0.02), TimerMode::Repeating),
+        ammo_cost: 1,
+        bullet: BulletSpawner::new(BulletBundle {
+            transform: Transform::from_xyz(0.0, 0.0, 0.0),
+            collider: Collider { radius: 4.0 },
+            sprite: Sprite {
+                image: assets.bullet1.clone(),
+                color: Color::srgb(1.0, 0.3, 0.3),
+                custom_size: Some(Vec2::new(48.0, 6.0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .normal(Vec2::new(30.0, 0.0)),
+        salted: false,
+        damage,
+        phasing: true,
+        piercing: true,
     }
 }
 
@@ -89,7 +140,7 @@ fn make_rocketlauncher(assets: &TouhouAssets) -> Weapon {
         collider: Collider { radius: 20.0 },
         sprite: Sprite {
             image: assets.rocket.clone(),
-            custom_size: Some(Vec2::new(100.0, 100.0)),
+            custom_size: Some(Vec2::new(64.0, 64.0)),
             ..Default::default()
         },
         ..Default::default()
@@ -110,6 +161,7 @@ fn make_rocketlauncher(assets: &TouhouAssets) -> Weapon {
         salted: false,
         damage: 50,
         phasing: false,
+        piercing: false,
     }
 }
 
@@ -120,9 +172,53 @@ pub struct Weapon {
     bullet: BulletSpawner,
     salted: bool,
     phasing: bool,
+    piercing: bool,
     damage: u32,
 }
 
+/// A piece of equipment the player can bring into an encounter.
+///
+/// Replays store these as their discriminant, so only ever add variants at the end.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Tech {
+    MachineGun,
+    MachineGunT2,
+    Rocket,
+    AmmoStockpile,
+    HeavyBody,
+    MagicBullet,
+    EngineT1,
+    EngineT2,
+    Phase,
+    /// Every one adds a point of damage to a single laser stream.
+    LaserStream,
+    /// A smaller speed boost than the engines, stacks.
+    Thruster,
+    ExtraLife,
+}
+
+impl Tech {
+    const ALL: [Tech; 12] = [
+        Tech::MachineGun,
+        Tech::MachineGunT2,
+        Tech::Rocket,
+        Tech::AmmoStockpile,
+        Tech::HeavyBody,
+        Tech::MagicBullet,
+        Tech::EngineT1,
+        Tech::EngineT2,
+        Tech::Phase,
+        Tech::LaserStream,
+        Tech::Thruster,
+        Tech::ExtraLife,
+    ];
+
+    pub fn from_u8(value: u8) -> Option<Tech> {
+        Self::ALL.get(value as usize).copied()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) enum BulletType {
     Normal(NormalBullet),
@@ -155,6 +251,8 @@ pub fn config_loadout(
     let mut ammo_multiplier = 1.0;
     let mut damage_multiplier = 1.0;
 
+    let mut laser_damage = 0;
+
     for &(tech, alt) in loadout {
         let mut weapon_vec =
             |alt, weapon| if alt { &mut alt_weapons } else { &mut weapons }.push(weapon);
@@ -193,10 +291,15 @@ pub fn config_loadout(
                     phasing = true;
                 }
             }
-            _ => {}
+            Tech::LaserStream => laser_damage += 1,
+            Tech::Thruster => **speed *= 1.15,
+            Tech::ExtraLife => **life += 1,
         }
     }
 
+    // the laser already phases through everything, so it skips the salted/phasing mods below
+    let laser = (laser_damage > 0).then(|| make_laser_stream(assets, laser_damage));
+
     **ammo = (**ammo as f32 * ammo_multiplier) as u32;
 
     commands.entity(ent).with_children(|player| {
@@ -212,6 +315,10 @@ pub fn config_loadout(
             weapon.damage = (weapon.damage as f32 * damage_multiplier) as u32;
             player.spawn(weapon).insert(AltFire);
         }
+        if let Some(mut laser) = laser {
+            laser.damage = (laser.damage as f32 * damage_multiplier) as u32;
+            player.spawn(laser);
+        }
     });
 }
 
@@ -233,7 +340,8 @@ impl Weapon {
             damage: self.damage,
         })
         .insert_if(Salted, || self.salted)
-        .insert_if(Phasing, || self.phasing);
+        .insert_if(Phasing, || self.phasing)
+        .insert_if(Piercing, || self.piercing);
 
         self.bullet.clone().add_components(&mut ent);
     }
@@ -243,43 +351,47 @@ fn set_alt_fire(
     mut commands: Commands,
     player: PlayerQ<Entity>,
     mut weapons: Query<&mut Weapon>,
-    input: Res<ButtonInput<KeyCode>>,
+    input: Res<TouhouInput>,
 ) {
     let mut player = commands.entity(*player);
 
-    if input.just_pressed(KeyCode::ShiftLeft) || input.just_released(KeyCode::ShiftLeft) {
+    if input.just_pressed(InputFrame::ALT_FIRE) || input.just_released(InputFrame::ALT_FIRE) {
         for mut weapon in &mut weapons {
             weapon.timer.reset();
         }
     }
-    if input.pressed(KeyCode::ShiftLeft) {
+    if input.pressed(InputFrame::ALT_FIRE) {
         player.insert(AltFire);
     } else {
         player.remove::<AltFire>();
     }
 }
 
+/// Vertical distance between the player's guns, pulled in while focused.
+const WEAPON_SPACING: f32 = 50.0;
+const FOCUSED_WEAPON_SPACING: f32 = 15.0;
+
 fn fire_weapons(
     time: Res<Time>,
     mut commands: Commands,
     mut weapons: Query<(&mut Weapon, Option<&AltFire>)>,
-    player: PlayerQ<(&Transform, &mut Ammo, Option<&AltFire>)>,
+    player: PlayerQ<(&Transform, &mut Ammo, Option<&AltFire>, Has<Focused>)>,
 ) {
-    let (trans, mut ammo, alt) = player.into_inner();
-    let ammo: &mut u32 = &mut **ammo;
+    let (trans, mut ammo, alt, focused) = player.into_inner();
+    let ammo: &mut u32 = &mut ammo;
 
     let pos = trans.translation.xy();
     let alt_fire = alt.is_some();
-    let (mut weapon_count, mut alt_weapon_count) = (0, 0);
-
-    for (weapon, is_alt) in &mut weapons {
-        if is_alt.is_some() != alt_fire {
-            alt_weapon_count += 1;
-        } else {
-            weapon_count += 1;
-        }
-    }
+    let weapon_count = weapons
+        .iter()
+        .filter(|(_, is_alt)| is_alt.is_some() == alt_fire)
+        .count();
 
+    let spacing = if focused {
+        FOCUSED_WEAPON_SPACING
+    } else {
+        WEAPON_SPACING
+    };
     let mut weapon_idx = 0;
 
     for (mut weapon, is_alt) in &mut weapons {
@@ -305,13 +417,90 @@ fn fire_weapons(
                 &mut commands,
                 pos - Vec2 {
                     x: 0.0,
-                    y: (((weapon_idx - 1) * 50) - (25 * (weapon_count - 1))) as f32,
+                    y: ((weapon_idx - 1) as f32 - (weapon_count - 1) as f32 / 2.0) * spacing,
                 },
             )
         }
     }
 }
 
+/// Ammo spent on a bomb when the player is out of bomb stock.
+const BOMB_AMMO_COST: u32 = 500;
+const BOMB_DAMAGE: u32 = 250;
+const BOMB_INVULNERABILITY_SECS: f32 = 2.0;
+const BOMB_FLASH_SECS: f32 = 0.5;
+
+/// Sent whenever the player bombs.
+#[derive(Event)]
+pub struct BombUsed;
+
+/// Sent whenever the player loses a life.
+#[derive(Event)]
+pub struct LifeLost;
+
+#[derive(Component, Deref, DerefMut)]
+struct BombFlash(Timer);
+
+/// Clears every enemy bullet in the arena and damages all enemies, paid for with a
+/// bomb or, failing that, a big chunk of ammo.
+fn use_bomb(
+    mut commands: Commands,
+    player: PlayerQ<(Entity, &mut Bombs, &mut Ammo)>,
+    enemy_bullets: Query<Entity, EnemyBullets>,
+    mut enemies: Query<&mut Health, With<EnemyMarker>>,
+    rect: Res<GameplayRect>,
+    mut bomb_used: EventWriter<BombUsed>,
+) {
+    let (ent, mut bombs, mut ammo) = player.into_inner();
+
+    if **bombs > 0 {
+        **bombs -= 1;
+    } else if **ammo >= BOMB_AMMO_COST {
+        **ammo -= BOMB_AMMO_COST;
+    } else {
+        return;
+    }
+
+    for bullet in &enemy_bullets {
+        commands.entity(bullet).despawn();
+    }
+
+    for mut health in &mut enemies {
+        **health = health.saturating_sub(BOMB_DAMAGE);
+    }
+
+    commands
+        .entity(ent)
+        .insert(Invulnerability(Timer::from_seconds(
+            BOMB_INVULNERABILITY_SECS,
+            TimerMode::Once,
+        )));
+
+    commands.spawn((
+        TouhouMarker,
+        BombFlash(Timer::from_seconds(BOMB_FLASH_SECS, TimerMode::Once)),
+        Sprite::from_color(Color::srgba(1.0, 1.0, 1.0, 0.8), rect.rect.size()),
+        Transform::from_translation(rect.rect.center().extend(1.0)),
+    ));
+
+    bomb_used.write(BombUsed);
+}
+
+fn fade_bomb_flash(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flashes: Query<(Entity, &mut BombFlash, &mut Sprite)>,
+) {
+    for (ent, mut flash, mut sprite) in &mut flashes {
+        flash.tick(time.delta());
+        sprite.color.set_alpha(0.8 * flash.fraction_remaining());
+
+        if flash.finished() {
+            commands.entity(ent).despawn();
+        }
+    }
+}
+
 #[derive(Component, Default, Clone, Debug)]
 pub struct BulletMarker;
 
@@ -346,7 +535,7 @@ pub struct HomingBullet {
     pub target: Target,
 }
 
-#[derive(Clone, Copy, Default, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Deserialize)]
 pub enum Target {
     #[default]
     Enemy,
@@ -390,8 +579,12 @@ pub struct Salted;
 #[derive(Component, Default)]
 pub struct Phasing;
 
+/// Keeps going after hitting an enemy instead of despawning.
+#[derive(Component, Default)]
+pub struct Piercing;
+
 #[derive(Event)]
-pub struct PlayerHit(Entity);
+pub struct PlayerHit(pub Entity);
 
 #[derive(Debug, Clone, Component, Default)]
 pub struct Velocity {
@@ -408,7 +601,7 @@ pub struct BulletHit {
 }
 
 #[derive(Event, Copy, Clone)]
-struct EnemyHit {
+pub struct EnemyHit {
     bullet: Entity,
     enemy: Entity,
 }
@@ -416,11 +609,11 @@ struct EnemyHit {
 pub fn process_enemy_hits(
     mut commands: Commands,
     mut hits: EventReader<EnemyHit>,
-    player_bullets: Query<(&PlayerBullet, Option<&Phasing>)>,
+    player_bullets: Query<(&PlayerBullet, Option<&Phasing>, Has<Piercing>)>,
     mut enemies: Query<&mut Health, With<EnemyMarker>>,
 ) {
     for &EnemyHit { enemy, bullet } in hits.read() {
-        let Ok((damage, phasing)) = player_bullets.get(bullet) else {
+        let Ok((damage, phasing, piercing)) = player_bullets.get(bullet) else {
             continue;
         };
 
@@ -431,7 +624,9 @@ pub fn process_enemy_hits(
         **enemy_health =
             enemy_health.saturating_sub(**damage * if phasing.is_some() { 1 } else { 2 });
 
-        commands.entity(bullet).try_despawn();
+        if !piercing {
+            commands.entity(bullet).try_despawn();
+        }
     }
 }
 
@@ -440,7 +635,9 @@ pub fn process_player_hits(
     mut hits: EventReader<PlayerHit>,
     mut bullet_hits: EventReader<BulletHit>,
     salted_bullets: Query<Entity, (PlayerBullets, With<Salted>)>,
+    lasers: Query<(), With<laser::Laser>>,
     player: Option<PlayerQ<(&mut Life, Option<&Invulnerability>)>>,
+    mut life_lost_writer: EventWriter<LifeLost>,
 ) {
     let Some((mut life, immortal)) = player.map(|p| p.into_inner()) else {
         return;
@@ -454,7 +651,7 @@ pub fn process_player_hits(
     let mut life_lost = false;
     for PlayerHit(ent) in hits.read() {
         // if something else already hit this bullet
-        if let Some(&player_bullet) = already_collided_bullets.get(&ent) {
+        if let Some(&player_bullet) = already_collided_bullets.get(ent) {
             // ... and if that something else was salted...
             if salted_bullets.contains(player_bullet) {
                 continue;
@@ -462,11 +659,14 @@ pub fn process_player_hits(
         }
 
         // try to get the bullet entity, but if it has already despawned, continue
-        let Some(mut bullet) = commands.get_entity(*ent) else {
+        let Ok(mut bullet) = commands.get_entity(*ent) else {
             continue;
         };
 
-        bullet.try_despawn();
+        // beams keep going through the player
+        if !lasers.contains(*ent) {
+            bullet.try_despawn();
+        }
 
         if life_lost || immortal.is_some() {
             continue;
@@ -474,22 +674,23 @@ pub fn process_player_hits(
 
         life.0 = life.0.saturating_sub(1);
         life_lost = true;
+        life_lost_writer.write(LifeLost);
     }
 }
 
 fn bullet_bullet_hit(
     mut commands: Commands,
     mut hits: EventReader<BulletHit>,
-    player_bullets: Query<(&NormalBullet, Option<&Salted>), PlayerBullets>,
-    enemy_bullets: Query<&NormalBullet, EnemyBullets>,
+    player_bullets: Query<Option<&Salted>, (With<NormalBullet>, PlayerBullets)>,
+    enemy_bullets: Query<(), (With<NormalBullet>, EnemyBullets)>,
 ) {
     for BulletHit { player, enemy } in hits.read() {
-        let Ok((p, salted)) = player_bullets.get(*player) else {
+        let Ok(salted) = player_bullets.get(*player) else {
             continue;
         };
-        let Ok(e) = enemy_bullets.get(*enemy) else {
+        if !enemy_bullets.contains(*enemy) {
             continue;
-        };
+        }
 
         commands.entity(*player).despawn();
         if salted.is_some() {
@@ -500,45 +701,38 @@ fn bullet_bullet_hit(
 
 fn check_player_bullets(
     mut hits: EventWriter<EnemyHit>,
-    player_bullets: Query<(Entity, &Transform, &Collider), PlayerBullets>,
+    grid: Res<BulletGrid>,
     enemies: Query<(Entity, &Transform, &Collider), With<enemy::EnemyMarker>>,
 ) {
-    for (bullet, b_trans, b_coll) in &player_bullets {
-        let b_circle = b_coll.to_circle(b_trans.translation.xy());
-        for (enemy, e_trans, e_coll) in &enemies {
-            let e_circle = e_coll.to_circle(e_trans.translation.xy());
+    for (enemy, e_trans, e_coll) in &enemies {
+        let e_circle = e_coll.to_circle(e_trans.translation.xy());
 
-            if b_circle.hits(e_circle) {
-                hits.send(EnemyHit { enemy, bullet });
-            }
+        for bullet in grid.player_bullets.hits(e_circle) {
+            hits.write(EnemyHit { enemy, bullet });
         }
     }
 }
 
 fn check_bullet_bullet(
     mut hits: EventWriter<BulletHit>,
+    grid: Res<BulletGrid>,
     player_bullets: Query<(Entity, &Transform, &Collider), (PlayerBullets, Without<Phasing>)>,
-    enemy_bullets: Query<(Entity, &Transform, &Collider), EnemyBullets>,
 ) {
     for (p, p_trans, p_coll) in &player_bullets {
         let player_circle = circle(p_trans, p_coll);
 
-        for (e, e_trans, e_coll) in &enemy_bullets {
-            let enemy_circle = circle(e_trans, e_coll);
-
-            if player_circle.hits(enemy_circle) {
-                hits.send(BulletHit {
-                    player: p,
-                    enemy: e,
-                });
-            }
+        for e in grid.enemy_bullets.hits(player_circle) {
+            hits.write(BulletHit {
+                player: p,
+                enemy: e,
+            });
         }
     }
 }
 
 fn check_enemy_bullets(
     player: PlayerQ<(&Transform, &Collider)>,
-    bullet_query: Query<(Entity, &Transform, &Collider), EnemyBullets>,
+    grid: Res<BulletGrid>,
     mut hit_writer: EventWriter<PlayerHit>,
 ) {
     let player_circle = {
@@ -546,11 +740,117 @@ fn check_enemy_bullets(
         circle(trans, coll)
     };
 
-    for (ent, trans, coll) in &bullet_query {
-        let bullet_circle = circle(trans, coll);
+    for ent in grid.enemy_bullets.hits(player_circle) {
+        hit_writer.write(PlayerHit(ent));
+    }
+}
+
+/// How far outside the player's hitbox a bullet can pass and still count as grazed.
+const GRAZE_MARGIN: f32 = 30.0;
+const GRAZE_SCORE: u64 = 500;
+const GRAZE_AMMO: u32 = 2;
+
+/// Marks enemy bullets that have already been grazed, so each one only counts once.
+#[derive(Component)]
+struct Grazed;
+
+fn check_grazes(
+    mut commands: Commands,
+    player: PlayerQ<(&Transform, &Collider, &mut Score, &mut Graze, &mut Ammo)>,
+    grid: Res<BulletGrid>,
+    grazed: Query<(), With<Grazed>>,
+) {
+    let (trans, coll, mut score, mut graze, mut ammo) = player.into_inner();
+    let hitbox = circle(trans, coll);
+    let graze_circle = Circle::new(hitbox.radius + GRAZE_MARGIN, hitbox.pos);
+
+    let hitting: Vec<_> = grid.enemy_bullets.hits(hitbox).collect();
+
+    for bullet in grid.enemy_bullets.hits(graze_circle) {
+        if grazed.contains(bullet) || hitting.contains(&bullet) {
+            continue;
+        }
+
+        commands.entity(bullet).try_insert(Grazed);
+        **score += GRAZE_SCORE;
+        **graze += 1;
+        **ammo += GRAZE_AMMO;
+    }
+}
+
+/// Turns every enemy bullet into a [`PointItem`], e.g. when a spellcard ends.
+#[derive(Event)]
+pub struct BulletCancel;
+
+const POINT_ITEM_SCORE: u64 = 100;
+/// Units per fixed tick.
+const POINT_ITEM_DRIFT_SPEED: f32 = 3.0;
+const POINT_ITEM_RUSH_SPEED: f32 = 20.0;
+/// How far past the hitbox items get picked up.
+const POINT_ITEM_PICKUP_MARGIN: f32 = 40.0;
+
+/// What's left of a cancelled bullet. Drifts toward the player, or rushes to them
+/// while they're past the middle of the arena.
+#[derive(Component)]
+struct PointItem;
+
+pub fn cancel_bullets(
+    mut commands: Commands,
+    mut events: EventReader<BulletCancel>,
+    assets: Res<TouhouAssets>,
+    bullets: Query<(Entity, &Transform), EnemyBullets>,
+) {
+    events.clear();
+
+    for (ent, trans) in &bullets {
+        commands.entity(ent).despawn();
+        commands.spawn((
+            TouhouMarker,
+            PointItem,
+            Transform::from_translation(trans.translation.xy().extend(0.0)),
+            Sprite {
+                image: assets.bullet1.clone(),
+                color: Color::srgb(1.0, 0.9, 0.3),
+                custom_size: Some(Vec2::splat(12.0)),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn move_point_items(
+    rect: Res<GameplayRect>,
+    player: PlayerQ<&Transform>,
+    mut items: Query<&mut Transform, (With<PointItem>, Without<PlayerMarker>)>,
+) {
+    let target = player.translation.xy();
+    let speed = if target.x > rect.rect.center().x {
+        POINT_ITEM_RUSH_SPEED
+    } else {
+        POINT_ITEM_DRIFT_SPEED
+    };
+
+    for mut trans in &mut items {
+        let pos = trans.translation.xy().move_towards(target, speed);
+        trans.translation = pos.extend(trans.translation.z);
+    }
+}
 
-        if bullet_circle.hits(player_circle) {
-            hit_writer.send(PlayerHit(ent));
+fn collect_point_items(
+    mut commands: Commands,
+    player: PlayerQ<(&Transform, &Collider, &mut Score)>,
+    items: Query<(Entity, &Transform), With<PointItem>>,
+) {
+    let (trans, coll, mut score) = player.into_inner();
+    let pickup = Circle::new(
+        coll.radius + POINT_ITEM_PICKUP_MARGIN,
+        trans.translation.xy(),
+    );
+
+    for (ent, item) in &items {
+        if pickup.hits(Circle::new(0.0, item.translation.xy())) {
+            commands.entity(ent).despawn();
+            **score += POINT_ITEM_SCORE;
         }
     }
 }
@@ -601,14 +901,7 @@ fn move_rotating_bullets(
 fn move_homing_bullets(
     time: Res<Time>,
     mut bullet_query: Query<
-        (
-            &mut Sprite,
-            &HomingBullet,
-            &mut NormalBullet,
-            &mut Velocity,
-            &Lifetime,
-            &mut Transform,
-        ),
+        (&HomingBullet, &mut NormalBullet, &Lifetime, &mut Transform),
         With<BulletMarker>,
     >,
     player: Option<Single<&Transform, (PlayerFilter, Without<BulletMarker>)>>,
@@ -618,7 +911,7 @@ fn move_homing_bullets(
     let enemy = enemy.map(|e| e.translation);
     let player = player.map(|p| p.translation);
 
-    for (mut sprite, bullet, mut normal, mut velocity, lifetime, mut trans) in &mut bullet_query {
+    for (bullet, mut normal, lifetime, mut trans) in &mut bullet_query {
         if bullet.target == Target::Enemy && enemy.is_none() {
             log::error!("can't find enemy");
         }
@@ -647,14 +940,9 @@ fn move_homing_bullets(
 }
 
 fn move_stutter_bullets(
-    mut bullet_query: Query<(
-        &mut StutterBullet,
-        &mut NormalBullet,
-        &Lifetime,
-        &mut Transform,
-    )>,
+    mut bullet_query: Query<(&mut StutterBullet, &mut NormalBullet, &Lifetime)>,
 ) {
-    for (mut bullet, mut velocity, lifetime, mut trans) in &mut bullet_query {
+    for (mut bullet, mut velocity, lifetime) in &mut bullet_query {
         if lifetime.0.elapsed_secs() < bullet.wait_time {
             velocity.velocity = Vec2::ZERO;
         } else if !bullet.has_started {
@@ -666,15 +954,9 @@ fn move_stutter_bullets(
 
 fn resolve_delayed_bullets(
     mut commands: Commands,
-    mut bullet_query: Query<(
-        Entity,
-        &mut DelayedBullet,
-        &mut NormalBullet,
-        &Lifetime,
-        &mut Transform,
-    )>,
+    mut bullet_query: Query<(Entity, &mut DelayedBullet, &Lifetime), With<NormalBullet>>,
 ) {
-    for (entity, mut bullet, mut velocity, lifetime, mut trans) in &mut bullet_query {
+    for (entity, mut bullet, lifetime) in &mut bullet_query {
         if lifetime.0.elapsed_secs() >= bullet.delay && !bullet.deployed {
             bullet.deployed = true;
             bullet
@@ -685,16 +967,8 @@ fn resolve_delayed_bullets(
     }
 }
 
-fn move_wave_bullets(
-    mut bullet_query: Query<(
-        &WaveBullet,
-        &mut Velocity,
-        &Lifetime,
-        &mut Transform,
-        &mut NormalBullet,
-    )>,
-) {
-    for (bullet, mut velocity, lifetime, mut trans, mut normal) in &mut bullet_query {
+fn move_wave_bullets(mut bullet_query: Query<(&WaveBullet, &Lifetime, &mut NormalBullet)>) {
+    for (bullet, lifetime, mut normal) in &mut bullet_query {
         normal.velocity =
             bullet.true_velocity * ((lifetime.0.elapsed_secs() * bullet.sine_mod).sin() + 1.0);
     }
@@ -0,0 +1,111 @@
+//! Difficulty presets. They're applied when the boss and player are spawned, by
+//! scaling the emitters and bullets from the boss file, so changing the difficulty
+//! mid-encounter only takes effect on the next one.
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+pub fn difficulty_plugin(app: &mut App) {
+    app.init_resource::<Difficulty>();
+}
+
+#[derive(
+    Resource,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Debug,
+)]
+#[repr(u8)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Lunatic,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 4] = [
+        Difficulty::Easy,
+        Difficulty::Normal,
+        Difficulty::Hard,
+        Difficulty::Lunatic,
+    ];
+
+    pub fn from_u8(value: u8) -> Option<Difficulty> {
+        Self::ALL.get(value as usize).copied()
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Lunatic => "Lunatic",
+        }
+    }
+
+    pub fn easier(self) -> Self {
+        Self::from_u8((self as u8).saturating_sub(1)).unwrap_or(self)
+    }
+
+    pub fn harder(self) -> Self {
+        Self::from_u8(self as u8 + 1).unwrap_or(self)
+    }
+
+    /// Multiplies the time between shots.
+    pub fn interval_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => 1.5,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 0.8,
+            Difficulty::Lunatic => 0.6,
+        }
+    }
+
+    /// Multiplies the number of bullets in a volley.
+    pub fn count_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.6,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.3,
+            Difficulty::Lunatic => 1.6,
+        }
+    }
+
+    pub fn speed_scale(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.8,
+            Difficulty::Normal => 1.0,
+            Difficulty::Hard => 1.15,
+            Difficulty::Lunatic => 1.3,
+        }
+    }
+
+    /// Lives the player starts with, before [`Tech::ExtraLife`] and the like.
+    pub fn lives(self) -> usize {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal | Difficulty::Hard => 3,
+            Difficulty::Lunatic => 2,
+        }
+    }
+
+    pub fn interval(self, secs: f32) -> f32 {
+        secs * self.interval_scale()
+    }
+
+    /// Never scales a volley down to nothing.
+    pub fn count(self, count: usize) -> usize {
+        ((count as f32 * self.count_scale()).round() as usize).max(1)
+    }
+}
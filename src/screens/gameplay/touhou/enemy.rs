@@ -4,49 +4,83 @@ use std::{
     time::Duration,
 };
 
-use bevy::{color, time::Stopwatch};
+use bevy::time::Stopwatch;
 use bullet::{
-    BulletBundle, BulletCommandExt, HomingBullet, NormalBullet, RotatingBullet, StutterBullet,
-    Target, WaveBullet,
+    BulletBundle, BulletCancel, BulletCommandExt, HomingBullet, NormalBullet, RotatingBullet,
+    StutterBullet, Target, WaveBullet,
 };
 
 use super::{
-    bullet::{DelayedBullet, Velocity},
+    boss::{BossDefinition, BossMovement, EmitterPattern},
+    bullet::DelayedBullet,
+    practice::{Practice, is_practiced},
     *,
 };
 
 pub fn enemy_plugin(app: &mut App) {
-    app.add_systems(OnEnter(GameState::Touhou), spawn_enemy)
-        .insert_resource(EncounterTime {
-            time: Stopwatch::new(),
-        })
-        .add_systems(Update, animate_sprites)
-        .add_systems(
-            OnEnter(GameState::Touhou),
-            |mut time: ResMut<EncounterTime>| time.time.reset(),
+    app.add_systems(
+        OnEnter(GameState::Touhou),
+        spawn_enemy.in_set(TouhouSets::EnterTouhou),
+    )
+    .insert_resource(EncounterTime {
+        time: Stopwatch::new(),
+    })
+    .add_systems(Update, animate_sprites)
+    .add_systems(
+        Update,
+        respawn_modified_boss
+            .run_if(on_event::<AssetEvent<BossDefinition>>)
+            .in_set(TouhouSets::Gameplay),
+    )
+    .add_systems(
+        OnEnter(GameState::Touhou),
+        (|mut time: ResMut<EncounterTime>| time.time.reset()).in_set(TouhouSets::EnterTouhou),
+    )
+    .add_systems(
+        FixedUpdate,
+        (
+            do_random_movement,
+            sync_emitter_transforms,
+            circular_rotating_emitter,
+            circular_homing_emitter,
+            circular_wave_emitter,
+            spray_emitter,
+            tentacle_emitter,
+            rotating_spray_emitter,
+            divisive_emitter,
+            flood_emitter,
+            laser_emitter,
+            advance_encounter_time,
+            process_spellcards,
         )
-        .add_systems(
-            FixedUpdate,
-            (
-                do_random_movement,
-                circular_rotating_emitter,
-                circular_homing_emitter,
-                circular_wave_emitter,
-                spray_emitter,
-                tentacle_emitter,
-                rotating_spray_emitter,
-                divisive_emitter,
-                flood_emitter,
-                advance_encounter_time,
-                process_spellcards,
-            )
-                .in_set(TouhouSets::Gameplay),
-        );
+            // a fixed order keeps the rng rolls, and so replays, deterministic
+            .chain()
+            .in_set(TouhouSets::Gameplay),
+    )
+    .add_systems(
+        FixedPostUpdate,
+        cancel_on_boss_damage
+            .after(bullet::process_enemy_hits)
+            .before(bullet::cancel_bullets)
+            .in_set(TouhouSets::Gameplay),
+    );
+}
+
+/// The only source of randomness during an encounter, seeded at its start so
+/// replays play out exactly the same.
+#[derive(Resource, Deref, DerefMut)]
+pub struct EncounterRng(StdRng);
+
+impl EncounterRng {
+    pub fn new(seed: u64) -> Self {
+        Self(StdRng::seed_from_u64(seed))
+    }
 }
 
+/// How far into the boss's spellcards the encounter is.
 #[derive(Resource)]
-struct EncounterTime {
-    time: Stopwatch,
+pub struct EncounterTime {
+    pub time: Stopwatch,
 }
 
 #[derive(Bundle, Default)]
@@ -125,6 +159,17 @@ pub struct DivisiveEmitter {
     rows: u64,
 }
 
+/// Fires `count` beams `spread` apart, centered on `angle`, which is measured from the
+/// direction of the player if `aimed`.
+#[derive(Component)]
+pub struct LaserEmitter {
+    beam: laser::Beam,
+    count: usize,
+    angle: f32,
+    spread: f32,
+    aimed: bool,
+}
+
 #[derive(Component, Clone, Default, Debug)]
 pub struct Animation {
     transition_time: Timer,
@@ -146,7 +191,7 @@ impl Animation {
 
 pub fn animate_sprites(time: Res<Time>, mut sprites: Query<(&mut Sprite, &mut Animation)>) {
     for (mut sprite, mut animation) in &mut sprites {
-        let Some(mut atlas) = sprite.texture_atlas.as_mut() else {
+        let Some(atlas) = sprite.texture_atlas.as_mut() else {
             continue;
         };
 
@@ -172,13 +217,23 @@ impl Emitter {
 }
 
 #[derive(Component, Default, Deref, DerefMut)]
-struct Active(bool);
+pub struct Active(bool);
 
 #[derive(Component)]
 struct Spellcard {
+    name: String,
     emitters: Vec<Entity>,
     start_time: f32,
     end_time: f32,
+    /// Whether the card was running last tick, to notice when it ends.
+    active: bool,
+}
+
+/// Cancels the bullets every time the boss loses another spellcard's worth of health.
+#[derive(Component)]
+struct CancelThreshold {
+    step: u32,
+    next: u32,
 }
 
 fn advance_encounter_time(
@@ -193,18 +248,44 @@ fn advance_encounter_time(
     }
 }
 
+fn cancel_on_boss_damage(
+    mut bosses: Query<(&Health, &mut CancelThreshold)>,
+    mut cancel: EventWriter<BulletCancel>,
+) {
+    for (health, mut threshold) in &mut bosses {
+        if threshold.step == 0 || **health > threshold.next {
+            continue;
+        }
+
+        while threshold.next > 0 && **health <= threshold.next {
+            threshold.next = threshold.next.saturating_sub(threshold.step);
+        }
+        cancel.write(BulletCancel);
+    }
+}
+
 fn process_spellcards(
     enc_time: Res<EncounterTime>,
-    cards: Query<&Spellcard>,
+    practice: Option<Res<Practice>>,
+    mut cards: Query<&mut Spellcard>,
     mut emitters: Query<&mut Active, With<Emitter>>,
+    mut cancel: EventWriter<BulletCancel>,
 ) {
     for mut active in &mut emitters {
         **active = false;
     }
     let current_time = enc_time.time.elapsed_secs();
 
-    for card in &cards {
-        if card.start_time < current_time && current_time < card.end_time {
+    for mut card in &mut cards {
+        let active = is_practiced(practice.as_deref(), &card.name)
+            && card.start_time < current_time
+            && current_time < card.end_time;
+        if card.active && !active {
+            cancel.write(BulletCancel);
+        }
+        card.active = active;
+
+        if active {
             for ent in &card.emitters {
                 let Ok(mut active) = emitters.get_mut(*ent) else {
                     continue;
@@ -264,6 +345,23 @@ impl BulletSpawner {
         }
     }
 
+    /// Speeds up every part of the bullet that moves in a straight line.
+    pub fn scale_speed(mut self, scale: f32) -> Self {
+        if let Some(normal) = &mut self.normal {
+            normal.velocity *= scale;
+        }
+        if let Some(stutter) = &mut self.stutter {
+            stutter.initial_velocity *= scale;
+        }
+        if let Some(wave) = &mut self.wave {
+            wave.true_velocity *= scale;
+        }
+        if let Some(delayed) = &mut self.delayed {
+            delayed.bullet = delayed.bullet.clone().scale_speed(scale);
+        }
+        self
+    }
+
     pub fn new(bullet: BulletBundle) -> Self {
         Self {
             bullet,
@@ -349,7 +447,11 @@ impl RandomMovement {
     }
 }
 
-fn do_random_movement(time: Res<Time>, mut query: Query<(&mut Transform, &mut RandomMovement)>) {
+fn do_random_movement(
+    time: Res<Time>,
+    mut rng: ResMut<EncounterRng>,
+    mut query: Query<(&mut Transform, &mut RandomMovement)>,
+) {
     for (mut trans, mut movement) in &mut query {
         let RandomMovement {
             next_move_timer,
@@ -374,7 +476,6 @@ fn do_random_movement(time: Res<Time>, mut query: Query<(&mut Transform, &mut Ra
         }
 
         if next_move_timer.finished() {
-            let mut rng = rand::rng();
             time_since_last_move.reset();
             next_move_timer.reset();
             next_move_timer.set_duration(Duration::from_secs_f32(
@@ -390,27 +491,35 @@ fn do_random_movement(time: Res<Time>, mut query: Query<(&mut Transform, &mut Ra
     }
 }
 
+/// Emitters spawn bullets from their [`GlobalTransform`], which bevy only propagates
+/// in `PostUpdate`. When a frame runs several fixed ticks that would be where the boss
+/// was at the last frame, not this tick, and replays wouldn't match their recordings.
+/// The boss is a root entity, so its `Transform` is already where it is in the world.
+fn sync_emitter_transforms(
+    bosses: Query<&Transform, With<HasEmitters>>,
+    mut emitters: Query<(&ChildOf, &Transform, &mut GlobalTransform), With<Emitter>>,
+) {
+    for (child_of, local, mut global) in &mut emitters {
+        let Ok(boss) = bosses.get(child_of.parent()) else {
+            continue;
+        };
+        *global = GlobalTransform::from(boss.mul_transform(*local));
+    }
+}
+
 fn divisive_emitter(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(
-        &GlobalTransform,
-        &mut Emitter,
-        &BulletSpawner,
-        &mut DivisiveEmitter,
-        &Active,
-    )>,
-    player: Single<&Transform, With<PlayerMarker>>,
+    mut query: Query<(&mut Emitter, &BulletSpawner, &mut DivisiveEmitter, &Active)>,
 ) {
-    let playerpos = player.into_inner();
-    for (trans, mut emitter, spawner, circ, active) in &mut query {
+    for (mut emitter, spawner, circ, active) in &mut query {
         if !**active {
             continue;
         }
 
         emitter.timer.tick(time.delta());
 
-        let mut bullet = spawner.bullet.clone();
+        let bullet = spawner.bullet.clone();
 
         if emitter.timer.finished() {
             emitter.timer.reset();
@@ -455,9 +564,7 @@ fn circular_rotating_emitter(
         &mut CircularAimedEmitter,
         &Active,
     )>,
-    player: Single<&Transform, With<PlayerMarker>>,
 ) {
-    let playerpos = player.into_inner();
     for (trans, mut emitter, spawner, circ, active) in &mut query {
         if !**active {
             continue;
@@ -477,7 +584,6 @@ fn circular_rotating_emitter(
                 let mut bullet = bullet.clone();
                 let dir = Vec2::from_angle(ang * i as f32);
                 bullet.transform.translation += (dir * circ.offset).extend(0.0);
-                let player_dir = playerpos.translation.xy() - bullet.transform.translation.xy();
 
                 let mut commands = commands.spawn(bullet);
 
@@ -489,10 +595,10 @@ fn circular_rotating_emitter(
                     rotating.origin += trans.translation().xy();
                     commands.add_bullet(rotating);
                 }
-                if let Some(mut rotating) = spawner.stutter {
+                if let Some(rotating) = spawner.stutter {
                     commands.add_bullet(rotating);
                 }
-                if let Some(mut rotating) = spawner.homing {
+                if let Some(rotating) = spawner.homing {
                     commands.add_bullet(rotating);
                 }
             }
@@ -510,9 +616,7 @@ fn tentacle_emitter(
         &mut TentacleEmitter,
         &Active,
     )>,
-    player: Single<&Transform, With<PlayerMarker>>,
 ) {
-    let playerpos = player.into_inner();
     for (trans, mut emitter, spawner, circ, active) in &mut query {
         if !**active {
             continue;
@@ -532,7 +636,6 @@ fn tentacle_emitter(
                 let mut bullet = bullet.clone();
                 let dir = Vec2::from_angle(ang * i as f32);
                 bullet.transform.translation += (dir * circ.offset).extend(0.0);
-                let player_dir = playerpos.translation.xy() - bullet.transform.translation.xy();
 
                 let mut commands = commands.spawn(bullet);
 
@@ -540,7 +643,7 @@ fn tentacle_emitter(
                     let velocity = dir.rotate(normal.velocity);
                     commands.add_bullet(NormalBullet { velocity });
                 }
-                let mut delayed_spawner = BulletSpawner::new(BulletBundle {
+                let delayed_spawner = BulletSpawner::new(BulletBundle {
                     collider: Collider { radius: 5.0 },
                     sprite: Sprite {
                         image: spawner.bullet.sprite.image.clone(),
@@ -571,7 +674,7 @@ fn spray_emitter(
         &Active,
     )>,
     player: Single<&Transform, With<PlayerMarker>>,
-    mut gizmos: Gizmos,
+    mut rng: ResMut<EncounterRng>,
 ) {
     let playerpos = player.translation.xy();
     for (trans, mut emitter, spawner, mut spray, active) in &mut query {
@@ -579,7 +682,6 @@ fn spray_emitter(
             continue;
         }
 
-        let mut rng = rand::rng();
         emitter.timer.tick(time.delta());
 
         let mut bullet = spawner.bullet.clone();
@@ -587,9 +689,9 @@ fn spray_emitter(
         bullet.transform.translation += trans.translation();
 
         if emitter.timer.elapsed_secs() < spray.firing_time {
-            spray.count += (time.delta().as_secs_f32() / spray.firing_speed);
+            spray.count += time.delta().as_secs_f32() / spray.firing_speed;
             for _ in 0..(spray.count as u64) {
-                let mut bullet = bullet.clone();
+                let bullet = bullet.clone();
                 let ang = Vec2::from_angle(
                     rng.random_range((spray.spray_width / -2.0)..=(spray.spray_width / 2.0)),
                 );
@@ -626,16 +728,13 @@ fn rotating_spray_emitter(
         &mut RotatingSprayEmitter,
         &Active,
     )>,
-    player: Single<&Transform, With<PlayerMarker>>,
-    mut gizmos: Gizmos,
+    mut rng: ResMut<EncounterRng>,
 ) {
-    let playerpos = player.translation.xy();
     for (trans, mut emitter, spawner, mut spray, active) in &mut query {
         if !**active {
             continue;
         }
 
-        let mut rng = rand::rng();
         emitter.timer.tick(time.delta());
 
         let mut bullet = spawner.bullet.clone();
@@ -645,9 +744,9 @@ fn rotating_spray_emitter(
 
         if emitter.timer.elapsed_secs() < spray.firing_time {
             for i in 0..spray.spray_count {
-                spray.count += (time.delta().as_secs_f32() / spray.firing_speed);
+                spray.count += time.delta().as_secs_f32() / spray.firing_speed;
                 for _ in 0..(spray.count as u64) {
-                    let mut bullet = bullet.clone();
+                    let bullet = bullet.clone();
                     let ang = Vec2::from_angle(
                         rng.random_range((spray.spray_width / -2.0)..=(spray.spray_width / 2.0))
                             + spray.rotation
@@ -674,26 +773,17 @@ fn rotating_spray_emitter(
 fn flood_emitter(
     mut commands: Commands,
     time: Res<Time>,
-    mut query: Query<(
-        &GlobalTransform,
-        &mut Emitter,
-        &BulletSpawner,
-        &mut FloodEmitter,
-        &Active,
-    )>,
-    player: Single<&Transform, With<PlayerMarker>>,
-    mut gizmos: Gizmos,
+    mut query: Query<(&mut Emitter, &BulletSpawner, &mut FloodEmitter, &Active)>,
+    mut rng: ResMut<EncounterRng>,
 ) {
-    let playerpos = player.translation.xy();
-    for (trans, mut emitter, spawner, mut spray, active) in &mut query {
+    for (mut emitter, spawner, spray, active) in &mut query {
         if !**active {
             continue;
         }
 
-        let mut rng = rand::rng();
         emitter.timer.tick(time.delta());
 
-        let mut bullet = spawner.bullet.clone();
+        let bullet = spawner.bullet.clone();
 
         if emitter.timer.finished() {
             emitter.timer.reset();
@@ -715,7 +805,44 @@ fn flood_emitter(
     }
 }
 
-const BULLET_SIZE: f32 = 15.0;
+pub fn laser_emitter(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(&ChildOf, &mut Emitter, &LaserEmitter, &Active)>,
+    sources: Query<&Transform>,
+    player: Single<&Transform, With<PlayerMarker>>,
+) {
+    let playerpos = player.translation.xy();
+    for (child_of, mut emitter, lasers, active) in &mut query {
+        if !**active {
+            continue;
+        }
+
+        emitter.timer.tick(time.delta());
+
+        if emitter.timer.finished() {
+            emitter.timer.reset();
+
+            // beams come out of the boss itself, its transform is up to date on every
+            // fixed tick unlike the emitter's global one
+            let source = child_of.parent();
+            let Ok(source_trans) = sources.get(source) else {
+                continue;
+            };
+
+            let mut angle = lasers.angle;
+            if lasers.aimed {
+                angle += (playerpos - source_trans.translation.xy()).to_angle();
+            }
+
+            let first = angle - lasers.spread * (lasers.count - 1) as f32 / 2.0;
+            for i in 0..lasers.count {
+                let angle = first + lasers.spread * i as f32;
+                commands.spawn(laser::laser(source, lasers.beam, angle));
+            }
+        }
+    }
+}
 
 fn circular_wave_emitter(
     mut commands: Commands,
@@ -727,9 +854,7 @@ fn circular_wave_emitter(
         &mut CircularWaveEmitter,
         &Active,
     )>,
-    player: Single<&Transform, With<PlayerMarker>>,
 ) {
-    let playerpos = player.into_inner();
     for (trans, mut emitter, spawner, mut circ, active) in &mut query {
         if !**active {
             continue;
@@ -749,7 +874,6 @@ fn circular_wave_emitter(
                 let mut bullet = bullet.clone();
                 let dir = Vec2::from_angle(ang * i as f32 + circ.rotation);
                 bullet.transform.translation += (dir * circ.offset).extend(0.0);
-                let player_dir = playerpos.translation.xy() - bullet.transform.translation.xy();
 
                 let mut commands = commands.spawn(bullet);
 
@@ -757,7 +881,7 @@ fn circular_wave_emitter(
                     let velocity = dir.rotate(normal.velocity);
                     commands.add_bullet(NormalBullet { velocity });
                 }
-                if let Some(mut rotating) = spawner.wave {
+                if let Some(rotating) = spawner.wave {
                     let velocity = dir.rotate(rotating.true_velocity);
                     commands.add_bullet(WaveBullet {
                         sine_mod: rotating.sine_mod,
@@ -781,9 +905,7 @@ fn circular_homing_emitter(
         &mut CircularHomingEmitter,
         &Active,
     )>,
-    player: Single<&Transform, With<PlayerMarker>>,
 ) {
-    let playerpos = player.into_inner();
     for (trans, mut emitter, spawner, mut circ, active) in &mut query {
         if !**active {
             continue;
@@ -802,7 +924,6 @@ fn circular_homing_emitter(
             let mut bullet = bullet.clone();
             let dir = Vec2::from_angle(ang * circ.idx as f32);
             bullet.transform.translation += (dir * circ.offset).extend(0.0);
-            let player_dir = playerpos.translation.xy() - bullet.transform.translation.xy();
 
             let mut commands = commands.spawn(bullet);
 
@@ -810,7 +931,7 @@ fn circular_homing_emitter(
                 let velocity = dir.rotate(normal.velocity);
                 commands.add_bullet(NormalBullet { velocity });
             }
-            if let Some(mut rotating) = spawner.homing {
+            if let Some(rotating) = spawner.homing {
                 commands.add_bullet(rotating);
             }
 
@@ -823,13 +944,13 @@ fn circular_homing_emitter(
 }
 
 pub trait EmitterExt {
-    fn spawn_spellcard<F>(&mut self, start_time: f32, end_time: f32, f: F) -> &mut Self
+    fn spawn_spellcard<F>(&mut self, name: &str, start_time: f32, end_time: f32, f: F) -> &mut Self
     where
         F: FnOnce(&mut SpellcardBuilder<'_, '_>);
 }
 
 impl<'a> EmitterExt for EntityCommands<'a> {
-    fn spawn_spellcard<F>(&mut self, start_time: f32, end_time: f32, f: F) -> &mut Self
+    fn spawn_spellcard<F>(&mut self, name: &str, start_time: f32, end_time: f32, f: F) -> &mut Self
     where
         F: FnOnce(&mut SpellcardBuilder<'_, '_>),
     {
@@ -842,9 +963,11 @@ impl<'a> EmitterExt for EntityCommands<'a> {
 
             let SpellcardBuilder { emitters, builder } = spellcard_builder;
             builder.spawn(Spellcard {
+                name: name.to_owned(),
                 start_time,
                 end_time,
                 emitters,
+                active: false,
             });
         });
 
@@ -854,411 +977,219 @@ impl<'a> EmitterExt for EntityCommands<'a> {
 
 pub struct SpellcardBuilder<'a, 'b> {
     emitters: Vec<Entity>,
-    builder: &'b mut ChildBuilder<'a>,
+    builder: &'b mut ChildSpawnerCommands<'a>,
 }
 
-impl<'a, 'b> SpellcardBuilder<'a, 'b> {
-    pub fn emitter<'c>(&'c mut self, emitter: EmitterBundle) -> EntityCommands<'c> {
+impl SpellcardBuilder<'_, '_> {
+    pub fn emitter(&mut self, emitter: EmitterBundle) -> EntityCommands<'_> {
         let builder = self.builder.spawn(emitter);
         self.emitters.push(builder.id());
         builder
     }
 }
 
-pub fn spawn_enemy(mut commands: Commands, assets: Res<TouhouAssets>, params: Res<MissionParams>) {
-    let big = Some(Vec2::splat(BULLET_SIZE * 3.0));
-    let medium = Some(Vec2::splat(BULLET_SIZE * 2.0));
-    let small = Some(Vec2::splat(BULLET_SIZE));
-
-    let bullet =
-        |tex: &Handle<Image>, layout: &Handle<TextureAtlasLayout>, size, anim| BulletBundle {
-            collider: Collider { radius: 5.0 },
-            sprite: Sprite {
-                image: tex.clone(),
-                custom_size: size,
-                texture_atlas: Some(TextureAtlas {
-                    layout: layout.clone(),
-                    index: 0,
-                }),
-                ..Default::default()
-            },
-            animation: Animation::new(0.1, anim, 0),
-            ..Default::default()
-        };
+pub fn spawn_enemy(
+    mut commands: Commands,
+    assets: Res<TouhouAssets>,
+    bosses: Res<Assets<BossDefinition>>,
+    difficulty: Res<Difficulty>,
+) {
+    let Some(boss) = bosses.get(&assets.flagship_boss) else {
+        log::error!("flagship boss definition isn't loaded");
+        return;
+    };
+    spawn_boss(&mut commands, boss, *difficulty);
+}
 
-    let red_girl_bullet = bullet(
-        &assets.girl_bullet_sheet,
-        &assets.girl_bullet_layout,
-        big,
-        2,
-    );
-    let red_girl_bullet_2 = bullet(
-        &assets.girl_bullet2_sheet,
-        &assets.girl_bullet2_layout,
-        big,
-        2,
-    );
-    let lizard_bullet = bullet(
-        &assets.lizard_bullet_sheet,
-        &assets.lizard_bullet_layout,
-        medium,
-        3,
-    );
-    let tentacle_bullet = BulletBundle {
-        collider: Collider { radius: 5.0 },
-        sprite: Sprite {
-            image: assets.bullet1.clone(),
-            custom_size: small,
-            ..Default::default()
+/// Restarts the encounter when the boss file is edited, so patterns can be tweaked live.
+fn respawn_modified_boss(
+    mut commands: Commands,
+    mut events: EventReader<AssetEvent<BossDefinition>>,
+    assets: Res<TouhouAssets>,
+    bosses: Res<Assets<BossDefinition>>,
+    enemies: Query<Entity, With<EnemyMarker>>,
+    mut enc_time: ResMut<EncounterTime>,
+    difficulty: Res<Difficulty>,
+    mut cancel: EventWriter<BulletCancel>,
+) {
+    let modified = events
+        .read()
+        .any(|event| event.is_modified(assets.flagship_boss.id()));
+    let Some(boss) = bosses.get(&assets.flagship_boss).filter(|_| modified) else {
+        return;
+    };
+
+    for enemy in &enemies {
+        commands.entity(enemy).despawn();
+    }
+    // the old patterns' bullets and beams don't match the file anymore
+    cancel.write(BulletCancel);
+    enc_time.time.reset();
+    spawn_boss(&mut commands, boss, *difficulty);
+    log::info!("reloaded boss `{}`", boss.name);
+}
+
+fn spawn_boss(commands: &mut Commands, boss: &BossDefinition, difficulty: Difficulty) {
+    let mut enemy = commands.spawn(EnemyBundle {
+        sprite: boss.sprite.clone(),
+        animation: boss.animation.clone(),
+        transform: Transform::from_translation(boss.position.extend(0.0))
+            .with_rotation(Quat::from_rotation_z(boss.rotation)),
+        collider: Collider {
+            radius: boss.collider_radius,
         },
+        health: Health(boss.health),
         ..Default::default()
-    };
-    let moon_girl_bullet = bullet(
-        &assets.moongirl_bullet_sheet,
-        &assets.moongirl_layout,
-        medium,
-        5,
-    );
+    });
+
+    let step = boss.health / boss.spellcards.len().max(1) as u32;
+    enemy.insert(CancelThreshold {
+        step,
+        next: boss.health.saturating_sub(step),
+    });
+
+    if let BossMovement::Random {
+        first_target,
+        move_time,
+        interval,
+    } = boss.movement
+    {
+        enemy.insert(RandomMovement {
+            next_move_timer: Timer::from_seconds(interval, TimerMode::Repeating),
+            move_start: boss.position,
+            move_end: first_target.into(),
+            move_time,
+            time_since_last_move: Stopwatch::new(),
+        });
+    }
 
-    match Enemies::MoonGirl {
-        Enemies::RedGirl => {
-            commands
-                .spawn(EnemyBundle {
-                    sprite: Sprite {
-                        image: assets.redgirl_sheet.clone(),
-                        custom_size: Some(Vec2::splat(150.0)),
-                        texture_atlas: Some(TextureAtlas {
-                            layout: assets.redgirl_layout.clone(),
-                            index: 0,
-                        }),
-                        ..Default::default()
-                    },
-                    animation: Animation::new(0.1, 3, 0),
-                    transform: Transform::from_xyz(200.0, 0.0, 0.0),
-                    collider: Collider { radius: 150.0 },
-                    health: Health(2000),
-                    ..Default::default()
-                })
-                .insert(RandomMovement {
-                    next_move_timer: Timer::from_seconds(10.0, TimerMode::Repeating),
-                    move_start: Vec2::new(200.0, 0.0),
-                    move_end: Vec2::new(500.0, 0.0),
-                    move_time: 3.0,
-                    time_since_last_move: Stopwatch::new(),
-                })
-                .spawn_spellcard(0.0, 25.0, |builder| {
-                    builder
-                        .emitter(make_emitter(
-                            0.05,
-                            BulletSpawner::new(red_girl_bullet.clone())
-                                .normal(Vec2::new(4.0, 0.0))
-                                .rotation(Vec2::ZERO, 0.0),
-                        ))
-                        .insert(CircularAimedEmitter {
-                            offset: 00.0,
-                            count: 8,
-                        });
-                    builder
-                        .emitter(make_emitter(
-                            0.25,
-                            BulletSpawner::new(red_girl_bullet_2.clone())
-                                .normal(Vec2::new(2.0, 0.0))
-                                .homing(4.0, TAU / 8.0, Target::Player),
-                        ))
-                        .insert(CircularHomingEmitter {
-                            offset: 150.0,
-                            count: 4,
-                            idx: 0,
-                        });
-                })
-                .spawn_spellcard(25.0, 45.0, |builder| {
-                    builder
-                        .emitter(make_emitter(
-                            1.5,
-                            BulletSpawner::new(red_girl_bullet.clone())
-                                .normal(Vec2::new(2.0, 0.0))
-                                .rotation(Vec2::ZERO, TAU / 64.0),
-                        ))
-                        .insert(CircularAimedEmitter {
-                            offset: 20.0,
-                            count: 48,
-                        });
-                    builder
-                        .emitter(make_emitter(
-                            1.5,
-                            BulletSpawner::new(red_girl_bullet.clone())
-                                .normal(Vec2::new(2.0, 0.0))
-                                .rotation(Vec2::ZERO, TAU / -64.0),
-                        ))
-                        .insert(CircularAimedEmitter {
-                            offset: 20.0,
-                            count: 48,
-                        });
-                })
-                .spawn_spellcard(45.0, 70.0, |builder| {
-                    builder
-                        .emitter(make_emitter(
-                            0.1,
-                            BulletSpawner::new(red_girl_bullet.clone())
-                                .normal(Vec2::new(2.0, 0.0))
-                                .wave(1.0, Vec2::new(2.0, 0.0)),
-                        ))
-                        .insert(CircularWaveEmitter {
-                            offset: 150.0,
-                            count: 6,
-                            rotation: 0.0,
-                            rotation_speed: 0.1,
-                        });
+    for card in &boss.spellcards {
+        enemy.spawn_spellcard(&card.name, card.start_time, card.end_time, |parent| {
+            for emitter in &card.emitters {
+                let spawner = emitter
+                    .spawner
+                    .clone()
+                    .scale_speed(difficulty.speed_scale());
+                let mut commands =
+                    parent.emitter(make_emitter(difficulty.interval(emitter.interval), spawner));
+                emitter.pattern.insert_into(&mut commands, difficulty);
+            }
+        });
+    }
+}
+
+impl EmitterPattern {
+    fn insert_into(self, commands: &mut EntityCommands<'_>, difficulty: Difficulty) {
+        match self {
+            EmitterPattern::CircularAimed { offset, count } => {
+                commands.insert(CircularAimedEmitter {
+                    offset,
+                    count: difficulty.count(count),
                 });
-        }
-        Enemies::Tentacle => {
-            commands
-                .spawn(EnemyBundle {
-                    sprite: Sprite {
-                        image: assets.tentacle.clone(),
-                        custom_size: Some(Vec2::splat(150.0)),
-                        ..Default::default()
-                    },
-                    transform: Transform::from_xyz(200.0, 0.0, 0.0),
-                    collider: Collider { radius: 150.0 },
-                    health: Health(1500),
-                    ..Default::default()
-                })
-                .insert(RandomMovement {
-                    next_move_timer: Timer::from_seconds(10.0, TimerMode::Repeating),
-                    move_start: Vec2::new(200.0, 0.0),
-                    move_end: Vec2::new(500.0, 0.0),
-                    move_time: 3.0,
-                    time_since_last_move: Stopwatch::new(),
-                })
-                .spawn_spellcard(0.0, 25.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            5.0,
-                            BulletSpawner::new(tentacle_bullet.clone()).normal(Vec2::new(4.0, 0.0)),
-                        ))
-                        .insert(SprayEmitter {
-                            spray_width: TAU,
-                            firing_time: 5.0,
-                            firing_speed: 0.05,
-                            count: 0.0,
-                        });
-                })
-                .spawn_spellcard(25.0, 45.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            0.05,
-                            BulletSpawner::new(tentacle_bullet.clone()).normal(Vec2::new(4.0, 0.0)),
-                        ))
-                        .insert(TentacleEmitter {
-                            offset: 100.0,
-                            count: 4,
-                        });
-                })
-                .spawn_spellcard(45.0, 70.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            1.4,
-                            BulletSpawner::new(tentacle_bullet.clone()).normal(Vec2::new(4.0, 0.0)),
-                        ))
-                        .insert(SprayEmitter {
-                            spray_width: 0.4,
-                            firing_time: 1.0,
-                            firing_speed: 0.01,
-                            count: 0.0,
-                        });
+            }
+            EmitterPattern::CircularHoming { offset, count } => {
+                commands.insert(CircularHomingEmitter {
+                    offset,
+                    count: difficulty.count(count),
+                    idx: 0,
                 });
-        }
-        Enemies::Lizard => {
-            commands
-                .spawn(EnemyBundle {
-                    sprite: Sprite {
-                        image: assets.lizard_sheet.clone(),
-                        custom_size: Some(Vec2::splat(150.0)),
-                        texture_atlas: Some(TextureAtlas {
-                            layout: assets.lizard_layout.clone(),
-                            index: 0,
-                        }),
-                        ..Default::default()
-                    },
-                    animation: Animation::new(0.1, 3, 0),
-                    transform: Transform::from_xyz(200.0, 0.0, 0.0),
-                    collider: Collider { radius: 150.0 },
-                    health: Health(2000),
-                    ..Default::default()
-                })
-                .insert(RandomMovement {
-                    next_move_timer: Timer::from_seconds(10.0, TimerMode::Repeating),
-                    move_start: Vec2::new(200.0, 0.0),
-                    move_end: Vec2::new(500.0, 0.0),
-                    move_time: 3.0,
-                    time_since_last_move: Stopwatch::new(),
-                })
-                .spawn_spellcard(0.0, 25.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            1.0,
-                            BulletSpawner::new(lizard_bullet.clone()).normal(Vec2::new(4.0, 0.0)),
-                        ))
-                        .insert(SprayEmitter {
-                            spray_width: TAU / 4.0,
-                            firing_time: 0.5,
-                            firing_speed: 0.02,
-                            count: 0.0,
-                        });
-                })
-                .spawn_spellcard(25.0, 45.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            1.0,
-                            BulletSpawner::new(lizard_bullet.clone()).normal(Vec2::new(4.0, 0.0)),
-                        ))
-                        .insert(RotatingSprayEmitter {
-                            spray_width: TAU / 4.0,
-                            firing_time: 1.0,
-                            firing_speed: 0.01,
-                            rotation_speed: TAU / 8.0,
-                            rotation: 0.0,
-                            count: 0.0,
-                            spray_count: 2,
-                        });
-                })
-                .spawn_spellcard(45.0, 70.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            5.0,
-                            BulletSpawner::new(lizard_bullet.clone())
-                                .normal(Vec2::new(4.0, 0.0))
-                                .rotation(Vec2::from((200.0, 0.0)), TAU / 16.0),
-                        ))
-                        .insert(SprayEmitter {
-                            spray_width: TAU,
-                            firing_time: 5.0,
-                            firing_speed: 0.01,
-                            count: 0.0,
-                        });
+            }
+            EmitterPattern::CircularWave {
+                offset,
+                count,
+                rotation_speed,
+            } => {
+                commands.insert(CircularWaveEmitter {
+                    offset,
+                    count: difficulty.count(count),
+                    rotation: 0.0,
+                    rotation_speed: rotation_speed.to_radians(),
                 });
-        }
-        Enemies::MoonGirl => {
-            commands
-                .spawn(EnemyBundle {
-                    sprite: Sprite {
-                        image: assets.kaguya_sheet.clone(),
-                        custom_size: Some(Vec2::splat(100.0)),
-                        texture_atlas: Some(TextureAtlas {
-                            layout: assets.kaguya_layout.clone(),
-                            index: 0,
-                        }),
-                        ..Default::default()
+            }
+            EmitterPattern::Tentacle { offset, count } => {
+                commands.insert(TentacleEmitter {
+                    offset,
+                    count: difficulty.count(count),
+                });
+            }
+            EmitterPattern::Flood { spray } => {
+                commands.insert(FloodEmitter {
+                    spray: spray.to_radians(),
+                });
+            }
+            EmitterPattern::Spray {
+                spray_width,
+                firing_time,
+                firing_speed,
+            } => {
+                commands.insert(SprayEmitter {
+                    spray_width: spray_width.to_radians(),
+                    firing_time,
+                    firing_speed: difficulty.interval(firing_speed),
+                    count: 0.0,
+                });
+            }
+            EmitterPattern::RotatingSpray {
+                spray_width,
+                firing_time,
+                firing_speed,
+                rotation_speed,
+                spray_count,
+            } => {
+                commands.insert(RotatingSprayEmitter {
+                    spray_width: spray_width.to_radians(),
+                    firing_time,
+                    firing_speed: difficulty.interval(firing_speed),
+                    count: 0.0,
+                    rotation_speed: rotation_speed.to_radians(),
+                    rotation: 0.0,
+                    spray_count,
+                });
+            }
+            EmitterPattern::Laser {
+                count,
+                length,
+                width,
+                warning_time,
+                firing_time,
+                angle,
+                spread,
+                rotation_speed,
+                sweep,
+                sweep_time,
+                aimed,
+            } => {
+                commands.insert(LaserEmitter {
+                    beam: laser::Beam {
+                        length,
+                        width,
+                        // easier difficulties get more time to get out of the way
+                        warning_time: difficulty.interval(warning_time),
+                        firing_time,
+                        rotation_speed: rotation_speed.to_radians() * difficulty.speed_scale(),
+                        sweep: sweep.to_radians(),
+                        sweep_time,
                     },
-                    animation: Animation::new(0.1, 5, 0),
-                    transform: Transform::from_xyz(200.0, 0.0, 0.0),
-                    collider: Collider { radius: 150.0 },
-                    health: Health(5000),
-                    ..Default::default()
-                })
-                .insert(RandomMovement {
-                    next_move_timer: Timer::from_seconds(10.0, TimerMode::Repeating),
-                    move_start: Vec2::new(200.0, 0.0),
-                    move_end: Vec2::new(500.0, 0.0),
-                    move_time: 3.0,
-                    time_since_last_move: Stopwatch::new(),
-                })
-                // em1
-                .spawn_spellcard(0.0, 25.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            0.05,
-                            BulletSpawner::new(moon_girl_bullet.clone())
-                                .normal(Vec2::new(5.0, 0.0)),
-                        ))
-                        .insert(DivisiveEmitter {
-                            columns: 19,
-                            rows: 11,
-                        });
-                })
-                // em2
-                .spawn_spellcard(25.0, 45.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            0.01,
-                            BulletSpawner::new(moon_girl_bullet.clone())
-                                .normal(Vec2::new(-5.0, 0.0)),
-                        ))
-                        .insert(FloodEmitter { spray: TAU / 16.0 });
-                })
-                // em12
-                .spawn_spellcard(0.0, 45.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            1.5,
-                            BulletSpawner::new(moon_girl_bullet.clone())
-                                .normal(Vec2::new(-2.0, 0.0))
-                                .rotation(Vec2::ZERO, TAU / 64.0),
-                        ))
-                        .insert(CircularAimedEmitter {
-                            offset: 1120.0,
-                            count: 64,
-                        });
-                })
-                // em3
-                .spawn_spellcard(45.0, 70.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            1.0,
-                            BulletSpawner::new(moon_girl_bullet.clone())
-                                .normal(Vec2::new(4.0, 0.0)),
-                        ))
-                        .insert(RotatingSprayEmitter {
-                            spray_width: TAU / 4.0,
-                            firing_time: 1.0,
-                            firing_speed: 0.01,
-                            rotation_speed: TAU / 8.0,
-                            rotation: 0.0,
-                            count: 0.0,
-                            spray_count: 2,
-                        });
-                })
-                //em4
-                .spawn_spellcard(70.0, 100.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            0.05,
-                            BulletSpawner::new(moon_girl_bullet.clone())
-                                .normal(Vec2::new(4.0, 0.0))
-                                .rotation(Vec2::ZERO, TAU / 16.0),
-                        ))
-                        .insert(CircularAimedEmitter {
-                            offset: 50.0,
-                            count: 24,
-                        });
-                    parent
-                        .emitter(make_emitter(
-                            0.05,
-                            BulletSpawner::new(moon_girl_bullet.clone())
-                                .normal(Vec2::new(4.0, 0.0))
-                                .rotation(Vec2::ZERO, TAU / -16.0),
-                        ))
-                        .insert(CircularAimedEmitter {
-                            offset: 50.0,
-                            count: 24,
-                        });
-                })
-                // em34
-                .spawn_spellcard(45.0, 100.0, |parent| {
-                    parent
-                        .emitter(make_emitter(
-                            4.0,
-                            BulletSpawner::new(moon_girl_bullet.clone())
-                                .normal(Vec2::new(4.0, 0.0))
-                                .stutter(1.0, Vec2::new(4.0, 0.0), false)
-                                .homing(3.0, TAU / 3.0, Target::Player),
-                        ))
-                        .insert(CircularAimedEmitter {
-                            offset: 150.0,
-                            count: 32,
-                        });
+                    // without a spread the extra beams would all overlap
+                    count: if spread > 0.0 {
+                        difficulty.count(count)
+                    } else {
+                        count
+                    },
+                    angle: angle.to_radians(),
+                    spread: spread.to_radians(),
+                    aimed,
                 });
+            }
+            EmitterPattern::Divisive { columns, rows } => {
+                // a grid, so split the scaling between both sides
+                let side =
+                    |n: u64| ((n as f32 * difficulty.count_scale().sqrt()).round() as u64).max(1);
+                commands.insert(DivisiveEmitter {
+                    columns: side(columns),
+                    rows: side(rows),
+                });
+            }
         }
     }
 }
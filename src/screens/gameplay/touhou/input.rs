@@ -0,0 +1,122 @@
+//! The player's input for one fixed tick of an encounter. Gameplay systems read
+//! [`TouhouInput`] instead of the keyboard, so a replay can stand in for the player.
+
+use super::*;
+
+pub fn input_plugin(app: &mut App) {
+    app.init_resource::<TouhouInput>()
+        .add_systems(OnEnter(GameState::Touhou), reset_input)
+        .add_systems(
+            FixedPreUpdate,
+            read_keyboard
+                .in_set(TouhouSets::Input)
+                .run_if(not(resource_exists::<replay::Playback>)),
+        );
+}
+
+/// Everything the player can press during an encounter, packed into a byte.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct InputFrame(u8);
+
+impl InputFrame {
+    pub const UP: Self = Self(1 << 0);
+    pub const DOWN: Self = Self(1 << 1);
+    pub const LEFT: Self = Self(1 << 2);
+    pub const RIGHT: Self = Self(1 << 3);
+    pub const FIRE: Self = Self(1 << 4);
+    pub const ALT_FIRE: Self = Self(1 << 5);
+    pub const BOMB: Self = Self(1 << 6);
+    pub const FOCUS: Self = Self(1 << 7);
+
+    pub fn from_bits(bits: u8) -> Self {
+        Self(bits)
+    }
+
+    pub fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn set(&mut self, other: Self, pressed: bool) {
+        if pressed {
+            self.0 |= other.0;
+        } else {
+            self.0 &= !other.0;
+        }
+    }
+}
+
+/// This tick's input, and the previous one's to detect presses.
+#[derive(Resource, Default, Debug)]
+pub struct TouhouInput {
+    current: InputFrame,
+    previous: InputFrame,
+}
+
+impl TouhouInput {
+    pub fn push(&mut self, frame: InputFrame) {
+        self.previous = self.current;
+        self.current = frame;
+    }
+
+    pub fn current(&self) -> InputFrame {
+        self.current
+    }
+
+    pub fn pressed(&self, button: InputFrame) -> bool {
+        self.current.contains(button)
+    }
+
+    pub fn just_pressed(&self, button: InputFrame) -> bool {
+        self.current.contains(button) && !self.previous.contains(button)
+    }
+
+    pub fn just_released(&self, button: InputFrame) -> bool {
+        !self.current.contains(button) && self.previous.contains(button)
+    }
+
+    /// The direction the player is holding, not normalized.
+    pub fn movement(&self) -> Vec2 {
+        let axis = |pos, neg| self.pressed(pos) as i32 as f32 - self.pressed(neg) as i32 as f32;
+        Vec2::new(
+            axis(InputFrame::RIGHT, InputFrame::LEFT),
+            axis(InputFrame::UP, InputFrame::DOWN),
+        )
+    }
+}
+
+/// Run condition for systems that should run while `button` is held.
+pub fn input_held(button: InputFrame) -> impl FnMut(Res<TouhouInput>) -> bool + Clone {
+    move |input: Res<TouhouInput>| input.pressed(button)
+}
+
+/// Run condition for systems that should run on the tick `button` goes down.
+pub fn input_tapped(button: InputFrame) -> impl FnMut(Res<TouhouInput>) -> bool + Clone {
+    move |input: Res<TouhouInput>| input.just_pressed(button)
+}
+
+fn reset_input(mut input: ResMut<TouhouInput>) {
+    *input = TouhouInput::default();
+}
+
+fn read_keyboard(keyboard: Res<ButtonInput<KeyCode>>, mut input: ResMut<TouhouInput>) {
+    let mut frame = InputFrame::default();
+
+    for (button, keys) in [
+        (InputFrame::UP, [KeyCode::KeyW, KeyCode::ArrowUp]),
+        (InputFrame::DOWN, [KeyCode::KeyS, KeyCode::ArrowDown]),
+        (InputFrame::LEFT, [KeyCode::KeyA, KeyCode::ArrowLeft]),
+        (InputFrame::RIGHT, [KeyCode::KeyD, KeyCode::ArrowRight]),
+    ] {
+        frame.set(button, keyboard.any_pressed(keys));
+    }
+    frame.set(InputFrame::FIRE, keyboard.pressed(KeyCode::KeyZ));
+    frame.set(InputFrame::ALT_FIRE, keyboard.pressed(KeyCode::ShiftLeft));
+    frame.set(InputFrame::BOMB, keyboard.pressed(KeyCode::KeyX));
+    frame.set(InputFrame::FOCUS, keyboard.pressed(KeyCode::KeyC));
+
+    input.push(frame);
+}
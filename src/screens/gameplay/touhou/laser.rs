@@ -0,0 +1,169 @@
+//! Boss beams. A beam shows up as a thin warning line first, so the player can see
+//! where it's going to be, then widens and hurts for a while, turning and sweeping
+//! as it goes. They're spawned by [`enemy::LaserEmitter`].
+
+use std::f32::consts::TAU;
+
+use bevy::{sprite::Anchor, time::Stopwatch};
+use bullet::{BombUsed, BulletCancel, PlayerHit};
+
+use super::*;
+
+/// Width of the warning line, it doesn't hurt.
+const WARNING_WIDTH: f32 = 2.0;
+const WARNING_COLOR: Color = Color::srgba(1.0, 0.3, 0.3, 0.6);
+const BEAM_COLOR: Color = Color::srgba(1.0, 0.35, 0.45, 0.9);
+
+pub fn laser_plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (update_lasers, check_lasers)
+            .chain()
+            .after(enemy::laser_emitter)
+            .in_set(TouhouSets::Gameplay),
+    )
+    .add_systems(
+        FixedPostUpdate,
+        cancel_lasers
+            .run_if(on_event::<BulletCancel>.or(on_event::<BombUsed>))
+            .in_set(TouhouSets::Gameplay),
+    );
+}
+
+/// How a beam looks and moves, shared by every beam an emitter fires.
+#[derive(Clone, Copy, Debug)]
+pub struct Beam {
+    pub length: f32,
+    pub width: f32,
+    pub warning_time: f32,
+    pub firing_time: f32,
+    /// Radians/s, only while firing.
+    pub rotation_speed: f32,
+    /// How far the beam swings to either side, in radians.
+    pub sweep: f32,
+    /// Seconds per swing back and forth, zero for no sweeping.
+    pub sweep_time: f32,
+}
+
+#[derive(Component)]
+pub struct Laser {
+    /// What the beam comes out of, it follows it around.
+    source: Entity,
+    beam: Beam,
+    /// Where the beam starts out pointing.
+    angle: f32,
+    age: Stopwatch,
+}
+
+impl Laser {
+    fn firing_secs(&self) -> Option<f32> {
+        let secs = self.age.elapsed_secs() - self.beam.warning_time;
+        (secs >= 0.0).then_some(secs)
+    }
+
+    fn current_angle(&self) -> f32 {
+        let Some(secs) = self.firing_secs() else {
+            return self.angle;
+        };
+        let sweep = if self.beam.sweep_time > 0.0 {
+            self.beam.sweep * (TAU * secs / self.beam.sweep_time).sin()
+        } else {
+            0.0
+        };
+
+        self.angle + self.beam.rotation_speed * secs + sweep
+    }
+
+    fn finished(&self) -> bool {
+        self.age.elapsed_secs() > self.beam.warning_time + self.beam.firing_time
+    }
+}
+
+pub fn laser(source: Entity, beam: Beam, angle: f32) -> impl Bundle {
+    (
+        Name::new("Laser"),
+        TouhouMarker,
+        Laser {
+            source,
+            beam,
+            angle,
+            age: Stopwatch::new(),
+        },
+        Sprite {
+            color: WARNING_COLOR,
+            custom_size: Some(Vec2::new(beam.length, WARNING_WIDTH)),
+            anchor: Anchor::CenterLeft,
+            ..default()
+        },
+        // hidden until it's been put in place
+        Transform::default(),
+        Visibility::Hidden,
+    )
+}
+
+fn update_lasers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lasers: Query<(
+        Entity,
+        &mut Laser,
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+    )>,
+    sources: Query<&Transform, Without<Laser>>,
+) {
+    for (ent, mut laser, mut trans, mut sprite, mut visibility) in &mut lasers {
+        laser.age.tick(time.delta());
+
+        let Ok(source) = sources.get(laser.source) else {
+            commands.entity(ent).despawn();
+            continue;
+        };
+        if laser.finished() {
+            commands.entity(ent).despawn();
+            continue;
+        }
+
+        trans.translation = source.translation;
+        trans.rotation = Quat::from_rotation_z(laser.current_angle());
+        *visibility = Visibility::Inherited;
+
+        if laser.firing_secs().is_some() {
+            sprite.color = BEAM_COLOR;
+            sprite.custom_size = Some(Vec2::new(laser.beam.length, laser.beam.width));
+        }
+    }
+}
+
+fn check_lasers(
+    lasers: Query<(Entity, &Laser, &Transform)>,
+    player: PlayerQ<(&Transform, &Collider)>,
+    mut hit_writer: EventWriter<PlayerHit>,
+) {
+    let (p_trans, p_coll) = player.into_inner();
+    let hitbox = p_coll.to_circle(p_trans.translation.xy());
+
+    for (ent, laser, trans) in &lasers {
+        if laser.firing_secs().is_none() {
+            continue;
+        }
+
+        let start = trans.translation.xy();
+        let beam = Capsule {
+            start,
+            end: start + Vec2::from_angle(laser.current_angle()) * laser.beam.length,
+            radius: laser.beam.width / 2.0,
+        };
+        if beam.hits(hitbox) {
+            hit_writer.write(PlayerHit(ent));
+        }
+    }
+}
+
+/// Beams go away with the bullets, when a spellcard ends or the player bombs.
+fn cancel_lasers(mut commands: Commands, lasers: Query<Entity, With<Laser>>) {
+    for ent in &lasers {
+        commands.entity(ent).despawn();
+    }
+}
@@ -0,0 +1,377 @@
+//! Spellcard practice, picked from the main menu. Jumps straight to one of the boss's
+//! spellcards instead of playing through the whole encounter, and ends when the card
+//! does. The best result for every card and [`Difficulty`] is kept in [`RECORDS_PATH`].
+//!
+//! A card counts as captured if it ends without the player losing a life or bombing.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use bevy::{input::common_conditions::input_just_pressed, time::Stopwatch, ui::Val::*};
+use bullet::{BombUsed, LifeLost};
+use enemy::EncounterTime;
+use serde::{Deserialize, Serialize};
+
+use crate::{asset_tracking::ResourceHandles, menus::Menu, theme::prelude::*};
+
+use super::*;
+
+const RECORDS_PATH: &str = "saves/practice.ron";
+
+pub fn practice_plugin(app: &mut App) {
+    app.init_resource::<PracticeSettings>()
+        .add_systems(Startup, load_records)
+        .add_systems(
+            Update,
+            spawn_practice_menu.run_if(
+                in_state(Menu::Practice)
+                    .and(resource_exists::<TouhouAssets>)
+                    .and(not(any_with_component::<PracticeMenu>)),
+            ),
+        )
+        .add_systems(
+            Update,
+            go_back.run_if(in_state(Menu::Practice).and(input_just_pressed(KeyCode::Escape))),
+        )
+        .add_systems(
+            OnEnter(Menu::Main),
+            return_to_practice_menu.run_if(resource_exists::<Practice>),
+        )
+        .add_systems(
+            Update,
+            start_practice.run_if(
+                resource_exists::<Practice>
+                    .and(in_state(GameState::OpenWorld))
+                    .and(any_with_component::<OpenWorldPlayer>),
+            ),
+        )
+        .add_systems(
+            OnEnter(GameState::Touhou),
+            skip_to_card
+                .after(TouhouSets::EnterTouhou)
+                .run_if(resource_exists::<Practice>),
+        )
+        .add_systems(
+            FixedPostUpdate,
+            track_attempt
+                .after(bullet::process_player_hits)
+                .before(on_death)
+                .in_set(TouhouSets::Gameplay)
+                .run_if(resource_exists::<Practice>),
+        )
+        .add_systems(
+            OnEnter(MissionState::Success),
+            save_attempt.run_if(resource_exists::<Practice>),
+        )
+        .add_systems(
+            OnEnter(MissionState::Fail),
+            save_attempt.run_if(resource_exists::<Practice>),
+        );
+}
+
+/// The spellcard being practiced. Stays around until the player is back in the
+/// practice menu.
+#[derive(Resource, Clone)]
+pub struct Practice {
+    boss: String,
+    card: String,
+    difficulty: Difficulty,
+    start_time: f32,
+    end_time: f32,
+    infinite_lives: bool,
+    /// Time since the card started.
+    time: Stopwatch,
+    /// When the player first lost a life, if they have.
+    missed_at: Option<f32>,
+    bombed: bool,
+}
+
+/// Whether the spellcard called `card` gets to run. While practicing, only the practiced
+/// card does, the ones overlapping it stay quiet.
+pub fn is_practiced(practice: Option<&Practice>, card: &str) -> bool {
+    practice.is_none_or(|practice| practice.card == card)
+}
+
+#[derive(Resource, Default)]
+struct PracticeSettings {
+    infinite_lives: bool,
+}
+
+#[derive(Resource, Serialize, Deserialize, Default)]
+struct PracticeRecords {
+    /// Difficulty to boss name to card name to record.
+    difficulties: BTreeMap<Difficulty, BTreeMap<String, BTreeMap<String, CardRecord>>>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, Copy)]
+struct CardRecord {
+    attempts: u32,
+    captures: u32,
+    /// The longest the player went without losing a life.
+    best_time: f32,
+}
+
+impl CardRecord {
+    fn describe(&self) -> String {
+        if self.attempts == 0 {
+            "Not attempted".to_string()
+        } else {
+            format!(
+                "Captured {}/{}, best {:.1}s",
+                self.captures, self.attempts, self.best_time
+            )
+        }
+    }
+}
+
+#[derive(Component)]
+struct PracticeMenu;
+
+fn load_records(mut commands: Commands) {
+    let records = match fs::read_to_string(RECORDS_PATH) {
+        Ok(file) => ron::from_str(&file).unwrap_or_else(|err| {
+            log::error!("could not read practice records, starting over: {err}");
+            PracticeRecords::default()
+        }),
+        Err(_) => PracticeRecords::default(),
+    };
+    commands.insert_resource(records);
+}
+
+fn spawn_practice_menu(
+    mut commands: Commands,
+    assets: Res<TouhouAssets>,
+    bosses: Res<Assets<BossDefinition>>,
+    records: Res<PracticeRecords>,
+    settings: Res<PracticeSettings>,
+    difficulty: Res<Difficulty>,
+) {
+    let Some(boss) = bosses.get(&assets.flagship_boss) else {
+        return;
+    };
+    let boss_records = records
+        .difficulties
+        .get(&*difficulty)
+        .and_then(|bosses| bosses.get(&boss.name));
+
+    commands
+        .spawn((
+            widget::ui_root("Practice Menu"),
+            GlobalZIndex(2),
+            StateScoped(Menu::Practice),
+            PracticeMenu,
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header(format!(
+                "Practice: {} ({})",
+                boss.name,
+                difficulty.name()
+            )));
+
+            parent
+                .spawn((
+                    Name::new("Spellcard Grid"),
+                    Node {
+                        display: Display::Grid,
+                        row_gap: Px(10.0),
+                        column_gap: Px(30.0),
+                        grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+                        ..default()
+                    },
+                ))
+                .with_children(|grid| {
+                    for card in &boss.spellcards {
+                        let practice = Practice {
+                            boss: boss.name.clone(),
+                            card: card.name.clone(),
+                            difficulty: *difficulty,
+                            start_time: card.start_time,
+                            end_time: card.end_time,
+                            infinite_lives: false,
+                            time: Stopwatch::new(),
+                            missed_at: None,
+                            bombed: false,
+                        };
+                        let record = boss_records
+                            .and_then(|cards| cards.get(&card.name))
+                            .copied()
+                            .unwrap_or_default();
+
+                        grid.spawn(spellcard_button(practice, record));
+                    }
+                });
+
+            let lives = if settings.infinite_lives { "on" } else { "off" };
+            parent.spawn((
+                Name::new("Practice Options"),
+                Node {
+                    column_gap: Px(30.0),
+                    ..default()
+                },
+                children![
+                    widget::button(format!("Infinite lives: {lives}"), toggle_infinite_lives),
+                    widget::button(
+                        format!("Difficulty: {}", difficulty.name()),
+                        cycle_difficulty
+                    ),
+                    widget::button("Back", go_back_on_click),
+                ],
+            ));
+        });
+}
+
+fn spellcard_button(practice: Practice, record: CardRecord) -> impl Bundle {
+    (
+        Name::new("Spellcard"),
+        Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        children![
+            widget::button(
+                practice.card.clone(),
+                move |_: Trigger<Pointer<Click>>,
+                      mut commands: Commands,
+                      settings: Res<PracticeSettings>,
+                      resource_handles: Res<ResourceHandles>,
+                      mut next_screen: ResMut<NextState<Screen>>| {
+                    commands.insert_resource(Practice {
+                        infinite_lives: settings.infinite_lives,
+                        ..practice.clone()
+                    });
+                    next_screen.set(if resource_handles.is_all_done() {
+                        Screen::Gameplay
+                    } else {
+                        Screen::Loading
+                    });
+                },
+            ),
+            widget::label(record.describe()),
+        ],
+    )
+}
+
+fn toggle_infinite_lives(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut settings: ResMut<PracticeSettings>,
+    menu: Single<Entity, With<PracticeMenu>>,
+) {
+    settings.infinite_lives = !settings.infinite_lives;
+    // respawned by `spawn_practice_menu` with the new label
+    commands.entity(*menu).despawn();
+}
+
+fn cycle_difficulty(
+    _: Trigger<Pointer<Click>>,
+    mut commands: Commands,
+    mut difficulty: ResMut<Difficulty>,
+    menu: Single<Entity, With<PracticeMenu>>,
+) {
+    *difficulty = match difficulty.harder() {
+        harder if harder == *difficulty => Difficulty::Easy,
+        harder => harder,
+    };
+    commands.entity(*menu).despawn();
+}
+
+fn go_back_on_click(_: Trigger<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::Main);
+}
+
+/// Practice ends on the title screen, go back to picking cards instead of the main menu.
+fn return_to_practice_menu(mut commands: Commands, mut next_menu: ResMut<NextState<Menu>>) {
+    commands.remove_resource::<Practice>();
+    next_menu.set(Menu::Practice);
+}
+
+fn start_practice(
+    mut mission_params: ResMut<MissionParams>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    *mission_params = MissionParams::default();
+    next_state.set(GameState::Touhou);
+}
+
+fn skip_to_card(
+    mut practice: ResMut<Practice>,
+    difficulty: Res<Difficulty>,
+    mut enc_time: ResMut<EncounterTime>,
+) {
+    enc_time
+        .time
+        .set_elapsed(Duration::from_secs_f32(practice.start_time));
+    practice.time.reset();
+    practice.difficulty = *difficulty;
+    practice.missed_at = None;
+    practice.bombed = false;
+}
+
+fn track_attempt(
+    time: Res<Time>,
+    mut practice: ResMut<Practice>,
+    mut life_lost: EventReader<LifeLost>,
+    mut bomb_used: EventReader<BombUsed>,
+    player: Option<PlayerQ<&mut Life>>,
+    mut mission_state: ResMut<NextState<MissionState>>,
+) {
+    practice.time.tick(time.delta());
+    let elapsed = practice.time.elapsed_secs();
+
+    if bomb_used.read().count() > 0 {
+        practice.bombed = true;
+    }
+
+    if life_lost.read().count() > 0 {
+        practice.missed_at.get_or_insert(elapsed);
+
+        if practice.infinite_lives
+            && let Some(mut life) = player
+        {
+            life.0 += 1;
+        }
+    }
+
+    if elapsed >= practice.end_time - practice.start_time {
+        mission_state.set(MissionState::Success);
+    }
+}
+
+fn save_attempt(
+    practice: Res<Practice>,
+    mission_state: Res<State<MissionState>>,
+    mut records: ResMut<PracticeRecords>,
+) {
+    let captured = *mission_state.get() == MissionState::Success
+        && practice.missed_at.is_none()
+        && !practice.bombed;
+    let survived = practice.missed_at.unwrap_or(practice.time.elapsed_secs());
+
+    let record = records
+        .difficulties
+        .entry(practice.difficulty)
+        .or_default()
+        .entry(practice.boss.clone())
+        .or_default()
+        .entry(practice.card.clone())
+        .or_default();
+    record.attempts += 1;
+    record.captures += captured as u32;
+    record.best_time = record.best_time.max(survived);
+
+    let saved = ron::ser::to_string_pretty(&*records, default())
+        .map_err(|err| err.to_string())
+        .and_then(|file| {
+            if let Some(dir) = Path::new(RECORDS_PATH).parent() {
+                fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+            }
+            fs::write(RECORDS_PATH, file).map_err(|err| err.to_string())
+        });
+    if let Err(err) = saved {
+        log::error!("could not save practice records: {err}");
+    }
+}
@@ -0,0 +1,351 @@
+//! Records every encounter tick by tick, and plays recordings back.
+//!
+//! An encounter is fully decided by its loadout, [`Difficulty`], the seed of the
+//! [`EncounterRng`] and the player's [`InputFrame`] on each fixed tick, so that's all
+//! a replay stores, along with how the encounter ended to check playback against.
+//! Recordings are saved to `replays/<seed>.ftvr` when an encounter ends.
+//!
+//! `--replay <file>` plays one back, `--check-replay <file>` does the same without a
+//! window and exits with an error if the result doesn't match.
+//!
+//! Nothing but the seed of the [`EncounterRng`] is stored, so replays only play back
+//! the same on the exact `rand` version they were recorded with, since `StdRng` makes
+//! no promises across versions. `Cargo.lock` isn't checked in, so a `cargo update`
+//! that bumps `rand` silently breaks every saved replay; bump [`VERSION`] when that
+//! happens.
+
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use enemy::EncounterRng;
+use input::{InputFrame, TouhouInput};
+use practice::Practice;
+
+use crate::LaunchArgs;
+
+use super::*;
+
+pub fn replay_plugin(app: &mut App) {
+    app.add_systems(Startup, load_replay_from_args)
+        .add_systems(
+            Update,
+            start_playback.run_if(
+                resource_exists::<Playback>
+                    .and(in_state(GameState::OpenWorld))
+                    .and(any_with_component::<OpenWorldPlayer>),
+            ),
+        )
+        .add_systems(
+            OnEnter(GameState::Touhou),
+            start_encounter
+                .before(bullet::config_loadout)
+                .in_set(TouhouSets::EnterTouhou),
+        )
+        .add_systems(
+            FixedPreUpdate,
+            (
+                read_playback
+                    .in_set(TouhouSets::Input)
+                    .run_if(resource_exists::<Playback>),
+                record_input
+                    .after(TouhouSets::Input)
+                    .in_set(TouhouSets::Gameplay)
+                    .run_if(resource_exists::<Recorder>),
+            ),
+        )
+        .add_systems(
+            FixedPostUpdate,
+            finish_encounter
+                .after(last_enemy_dead)
+                .after(on_death)
+                .in_set(TouhouSets::Gameplay)
+                .run_if(mission_ending),
+        )
+        .add_systems(OnExit(GameState::Touhou), stop_recording);
+}
+
+const MAGIC: &[u8; 4] = b"FTVR";
+const VERSION: u8 = 2;
+const REPLAY_DIR: &str = "replays";
+
+/// How an encounter ended, compared after playback.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EncounterResult {
+    pub lives: u32,
+    pub boss_health: u32,
+    pub score: u64,
+}
+
+#[derive(Clone, Debug)]
+pub struct Replay {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub loadout: Vec<(Tech, bool)>,
+    pub inputs: Vec<InputFrame>,
+    pub result: EncounterResult,
+}
+
+impl Replay {
+    pub fn write(&self, mut w: impl Write) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+        w.write_all(&[VERSION])?;
+        w.write_all(&self.seed.to_le_bytes())?;
+        w.write_all(&[self.difficulty as u8])?;
+
+        w.write_all(&[self.loadout.len() as u8])?;
+        for &(tech, alt) in &self.loadout {
+            w.write_all(&[tech as u8, alt as u8])?;
+        }
+
+        w.write_all(&(self.inputs.len() as u32).to_le_bytes())?;
+        let inputs: Vec<u8> = self.inputs.iter().map(|frame| frame.bits()).collect();
+        w.write_all(&inputs)?;
+
+        w.write_all(&self.result.lives.to_le_bytes())?;
+        w.write_all(&self.result.boss_health.to_le_bytes())?;
+        w.write_all(&self.result.score.to_le_bytes())?;
+        Ok(())
+    }
+
+    pub fn read(mut r: impl Read) -> io::Result<Self> {
+        fn invalid(msg: &str) -> io::Error {
+            io::Error::new(io::ErrorKind::InvalidData, msg)
+        }
+        fn bytes<const N: usize>(r: &mut impl Read) -> io::Result<[u8; N]> {
+            let mut buf = [0; N];
+            r.read_exact(&mut buf)?;
+            Ok(buf)
+        }
+
+        if &bytes::<4>(&mut r)? != MAGIC {
+            return Err(invalid("not a replay file"));
+        }
+        if bytes::<1>(&mut r)?[0] != VERSION {
+            return Err(invalid(
+                "replay was recorded by another version of the game",
+            ));
+        }
+        let seed = u64::from_le_bytes(bytes(&mut r)?);
+        let [difficulty] = bytes(&mut r)?;
+        let difficulty =
+            Difficulty::from_u8(difficulty).ok_or_else(|| invalid("unknown difficulty"))?;
+
+        let [loadout_len] = bytes(&mut r)?;
+        let loadout = (0..loadout_len)
+            .map(|_| {
+                let [tech, alt] = bytes(&mut r)?;
+                let tech = Tech::from_u8(tech).ok_or_else(|| invalid("unknown tech"))?;
+                Ok((tech, alt != 0))
+            })
+            .collect::<io::Result<_>>()?;
+
+        let input_len = u32::from_le_bytes(bytes(&mut r)?) as usize;
+        let mut inputs = vec![0; input_len];
+        r.read_exact(&mut inputs)?;
+        let inputs = inputs.into_iter().map(InputFrame::from_bits).collect();
+
+        let result = EncounterResult {
+            lives: u32::from_le_bytes(bytes(&mut r)?),
+            boss_health: u32::from_le_bytes(bytes(&mut r)?),
+            score: u64::from_le_bytes(bytes(&mut r)?),
+        };
+
+        Ok(Self {
+            seed,
+            difficulty,
+            loadout,
+            inputs,
+            result,
+        })
+    }
+
+    fn save(&self) -> io::Result<PathBuf> {
+        fs::create_dir_all(REPLAY_DIR)?;
+        let path = Path::new(REPLAY_DIR).join(format!("{:016x}.ftvr", self.seed));
+        self.write(io::BufWriter::new(fs::File::create(&path)?))?;
+        Ok(path)
+    }
+}
+
+/// The encounter being recorded. Only exists while the player is in control.
+#[derive(Resource)]
+struct Recorder {
+    seed: u64,
+    difficulty: Difficulty,
+    loadout: Vec<(Tech, bool)>,
+    inputs: Vec<InputFrame>,
+}
+
+/// A replay being played back instead of reading the keyboard.
+#[derive(Resource)]
+pub struct Playback {
+    replay: Replay,
+    tick: usize,
+    /// Exit once the replay is over, see [`LaunchArgs::check_replay`].
+    check: bool,
+}
+
+fn load_replay_from_args(
+    mut commands: Commands,
+    args: Res<LaunchArgs>,
+    mut next_screen: ResMut<NextState<Screen>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    let Some(path) = &args.replay else {
+        return;
+    };
+
+    match fs::File::open(path).and_then(|file| Replay::read(io::BufReader::new(file))) {
+        Ok(replay) => {
+            log::info!(
+                "playing back {} ({} ticks)",
+                path.display(),
+                replay.inputs.len()
+            );
+            commands.insert_resource(Playback {
+                replay,
+                tick: 0,
+                check: args.check_replay,
+            });
+            // skip the menus, the replay starts as soon as the game is loaded
+            next_screen.set(Screen::Loading);
+        }
+        Err(err) => {
+            log::error!("could not load replay {}: {err}", path.display());
+            if args.check_replay {
+                app_exit.write(AppExit::error());
+            }
+        }
+    }
+}
+
+fn start_playback(
+    playback: Res<Playback>,
+    mut mission_params: ResMut<MissionParams>,
+    mut difficulty: ResMut<Difficulty>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    mission_params.loadout = playback.replay.loadout.clone();
+    *difficulty = playback.replay.difficulty;
+    next_state.set(GameState::Touhou);
+}
+
+/// Seeds the encounter, and starts recording it unless it's a playback or practice.
+fn start_encounter(
+    mut commands: Commands,
+    playback: Option<Res<Playback>>,
+    practice: Option<Res<Practice>>,
+    mission_params: Res<MissionParams>,
+    difficulty: Res<Difficulty>,
+) {
+    let seed = match &playback {
+        Some(playback) => playback.replay.seed,
+        None => rand::random(),
+    };
+    commands.insert_resource(EncounterRng::new(seed));
+
+    if playback.is_none() && practice.is_none() {
+        commands.insert_resource(Recorder {
+            seed,
+            difficulty: *difficulty,
+            loadout: mission_params.loadout.clone(),
+            inputs: vec![],
+        });
+    }
+}
+
+fn read_playback(
+    mut commands: Commands,
+    mut playback: ResMut<Playback>,
+    mut input: ResMut<TouhouInput>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    let Some(&frame) = playback.replay.inputs.get(playback.tick) else {
+        log::error!(
+            "replay ran out after {} ticks before the encounter ended",
+            playback.tick
+        );
+        if playback.check {
+            app_exit.write(AppExit::error());
+        }
+        commands.remove_resource::<Playback>();
+        return;
+    };
+
+    playback.tick += 1;
+    input.push(frame);
+}
+
+fn record_input(input: Res<TouhouInput>, mut recorder: ResMut<Recorder>) {
+    recorder.inputs.push(input.current());
+}
+
+/// Whether this tick decided the encounter. Checked before the state actually changes,
+/// since more ticks can run in the same frame.
+fn mission_ending(next_state: Res<NextState<MissionState>>) -> bool {
+    matches!(
+        *next_state,
+        NextState::Pending(MissionState::Success | MissionState::Fail)
+    )
+}
+
+fn finish_encounter(
+    mut commands: Commands,
+    player: Option<PlayerQ<(&Life, &Score)>>,
+    enemies: Query<&Health, With<EnemyMarker>>,
+    recorder: Option<Res<Recorder>>,
+    playback: Option<Res<Playback>>,
+    mut app_exit: EventWriter<AppExit>,
+) {
+    let (lives, score) = player.map_or((0, 0), |player| {
+        let (life, score) = *player;
+        (**life as u32, **score)
+    });
+    let result = EncounterResult {
+        lives,
+        boss_health: enemies.iter().map(|health| **health).sum(),
+        score,
+    };
+
+    if let Some(recorder) = recorder {
+        let replay = Replay {
+            seed: recorder.seed,
+            difficulty: recorder.difficulty,
+            loadout: recorder.loadout.clone(),
+            inputs: recorder.inputs.clone(),
+            result,
+        };
+        match replay.save() {
+            Ok(path) => log::info!("saved replay to {}", path.display()),
+            Err(err) => log::error!("could not save replay: {err}"),
+        }
+        commands.remove_resource::<Recorder>();
+    }
+
+    if let Some(playback) = playback {
+        let expected = playback.replay.result;
+        let matches = expected == result;
+        if matches {
+            log::info!("replay reproduced the recorded result: {result:?}");
+        } else {
+            log::error!("replay diverged: expected {expected:?}, got {result:?}");
+        }
+
+        if playback.check {
+            app_exit.write(if matches {
+                AppExit::Success
+            } else {
+                AppExit::error()
+            });
+        }
+        commands.remove_resource::<Playback>();
+    }
+}
+
+/// Encounters left early (like the benchmark) aren't worth saving.
+fn stop_recording(mut commands: Commands) {
+    commands.remove_resource::<Recorder>();
+}
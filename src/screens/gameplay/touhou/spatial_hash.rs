@@ -0,0 +1,91 @@
+//! Uniform-grid broadphase for bullet collisions, so the hit checks scale with the
+//! number of nearby bullets instead of all of them.
+
+use std::collections::HashMap;
+
+use bullet::{EnemyBullets, PlayerBullets};
+
+use super::*;
+
+/// Roughly the size of the largest bullet; bigger colliders are still handled,
+/// they just widen every query a bit.
+const CELL_SIZE: f32 = 64.0;
+
+/// Every bullet, bucketed by the grid cell its center is in. Rebuilt each
+/// `FixedUpdate` after the bullets have moved.
+#[derive(Resource, Default)]
+pub struct BulletGrid {
+    pub enemy_bullets: SpatialHash,
+    pub player_bullets: SpatialHash,
+}
+
+#[derive(Default)]
+pub struct SpatialHash {
+    cells: HashMap<IVec2, Vec<(Entity, Circle)>>,
+    max_radius: f32,
+    len: usize,
+}
+
+impl SpatialHash {
+    fn cell(pos: Vec2) -> IVec2 {
+        (pos / CELL_SIZE).floor().as_ivec2()
+    }
+
+    fn clear(&mut self) {
+        // keep the buckets around, bullets tend to end up in the same cells again
+        for bucket in self.cells.values_mut() {
+            bucket.clear();
+        }
+        self.max_radius = 0.0;
+        self.len = 0;
+    }
+
+    fn insert(&mut self, entity: Entity, circle: Circle) {
+        self.cells
+            .entry(Self::cell(circle.pos))
+            .or_default()
+            .push((entity, circle));
+        self.max_radius = self.max_radius.max(circle.radius);
+        self.len += 1;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Every entity whose circle overlaps `circle`. Each entity is yielded at most once,
+    /// since it's only ever stored in one cell.
+    pub fn hits(&self, circle: Circle) -> impl Iterator<Item = Entity> + '_ {
+        let reach = Vec2::splat(circle.radius + self.max_radius);
+        let min = Self::cell(circle.pos - reach);
+        let max = Self::cell(circle.pos + reach);
+
+        (min.x..=max.x)
+            .flat_map(move |x| (min.y..=max.y).map(move |y| IVec2::new(x, y)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .filter(move |(_, other)| circle.hits(*other))
+            .map(|(entity, _)| *entity)
+    }
+}
+
+pub fn rebuild_bullet_grid(
+    mut grid: ResMut<BulletGrid>,
+    enemy_bullets: Query<(Entity, &Transform, &Collider), EnemyBullets>,
+    player_bullets: Query<(Entity, &Transform, &Collider), PlayerBullets>,
+) {
+    let BulletGrid {
+        enemy_bullets: enemy_grid,
+        player_bullets: player_grid,
+    } = &mut *grid;
+
+    enemy_grid.clear();
+    for (entity, trans, coll) in &enemy_bullets {
+        enemy_grid.insert(entity, coll.to_circle(trans.translation.xy()));
+    }
+
+    player_grid.clear();
+    for (entity, trans, coll) in &player_bullets {
+        player_grid.insert(entity, coll.to_circle(trans.translation.xy()));
+    }
+}
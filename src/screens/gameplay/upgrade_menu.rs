@@ -10,7 +10,6 @@ use super::{
         weapons::{self, BlackholeSpawner, WeaponAssets},
         Health,
     },
-    enemies::FlagshipAI,
     level::{MainOST, PlanetType, UIAssets, VisistedPlanet},
     player::Player,
     GameplayLogic,
@@ -79,7 +78,6 @@ pub fn update_upgrades(
     mut commands: Commands,
     weapon_assets: Res<WeaponAssets>,
     mut hp: Single<&mut Health, With<Player>>,
-    mut flagship_entity: Single<Entity, With<FlagshipAI>>,
     mut ost: Single<&mut AudioPlayer, With<MainOST>>,
     upgrades: Single<(Entity, &Upgrades), (With<Player>, Changed<Upgrades>)>,
 ) {
@@ -89,7 +87,6 @@ pub fn update_upgrades(
     let (ent, upgrades) = upgrades.into_inner();
 
     if upgrades.gotten_upgrades.get(&UpgradeTypes::Emp).is_some() {
-        commands.entity(*flagship_entity).insert(Health(1000));
         **ost = AudioPlayer::new(weapon_assets.boss_theme.clone());
     }
 
@@ -4,9 +4,12 @@ mod gameplay;
 mod loading;
 mod splash;
 mod title;
+mod victory;
 
 use bevy::prelude::*;
 
+pub use gameplay::{Difficulty, Galaxy, GalaxySeed};
+
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Screen>();
 
@@ -15,6 +18,7 @@ pub(super) fn plugin(app: &mut App) {
         loading::plugin,
         splash::plugin,
         title::plugin,
+        victory::plugin,
     ));
 }
 
@@ -27,4 +31,5 @@ pub enum Screen {
     Title,
     Loading,
     Gameplay,
+    Victory,
 }
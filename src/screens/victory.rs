@@ -0,0 +1,26 @@
+//! The screen shown after the flagship has been destroyed.
+
+use bevy::prelude::*;
+
+use crate::{screens::Screen, theme::prelude::*};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Victory), spawn_victory_screen);
+}
+
+fn spawn_victory_screen(mut commands: Commands) {
+    commands.spawn((
+        widget::ui_root("Victory Screen"),
+        GlobalZIndex(2),
+        StateScoped(Screen::Victory),
+        children![
+            widget::header("Victory"),
+            widget::label("The flagship has been destroyed. Earth is safe, for now."),
+            widget::button("Back to title", go_to_title),
+        ],
+    ));
+}
+
+fn go_to_title(_: Trigger<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}